@@ -0,0 +1,147 @@
+/*!
+Checks whether a user is allowed to watch an episode, so that `get_video()` is only called when it can succeed.
+
+```no_run
+use chrono::Utc;
+use roosterteeth_rs::access::can_watch;
+use roosterteeth_rs::requests::{Credential, Requests};
+
+let requests = Requests::new(Credential::Login("username", "password")).with_country("US".parse().unwrap());
+let user = requests.get_profile().unwrap();
+let episode = requests.get_episode(&"red-vs-blue-season-1-episode-1".parse().unwrap());
+
+match can_watch(&episode, Some(&user), requests.country(), Utc::now()) {
+	Ok(()) => println!("{}", requests.get_video(&episode.attributes.slug).unwrap().attributes.url),
+	Err(reason) => println!("Can't watch {}: {}", episode.attributes.title, reason),
+}
+```
+
+The check only uses the episode's metadata, the API has the final say and `get_video()` returns the same
+[VideoUnavailable](./enum.VideoUnavailable.html) reasons when it refuses a video.
+*/
+
+use chrono::offset::FixedOffset;
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use std::error;
+use std::fmt;
+
+use crate::geo::{Country, Restricted};
+use crate::schedule::{Availability, Tier};
+use crate::structs::episodes::Episode;
+use crate::structs::users::User;
+
+/// The reason a video can't be watched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VideoUnavailable {
+	/// The video is available to logged in users, but the client is anonymous.
+	NotLoggedIn,
+	/// The video hasn't gone live for the user's tier yet, along with when it will if that is known.
+	NotYetPublic(Option<DateTime<FixedOffset>>),
+	/// The video is only available to paying FIRST members.
+	SponsorOnly,
+	/// The video isn't available in the viewer's country, if known.
+	GeoBlocked(Option<Country>),
+	/// The API refused the video with a status that isn't otherwise understood.
+	Other(u16),
+}
+
+impl fmt::Display for VideoUnavailable {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			VideoUnavailable::NotLoggedIn => write!(f, "Video requires logging in."),
+			VideoUnavailable::NotYetPublic(Some(at)) => {
+				write!(f, "Video is not avaliable until {}.", at)
+			}
+			VideoUnavailable::NotYetPublic(None) => write!(f, "Video is not avaliable yet."),
+			VideoUnavailable::SponsorOnly => write!(f, "Video is only avaliable to FIRST members."),
+			VideoUnavailable::GeoBlocked(Some(country)) => {
+				write!(f, "Video is not avaliable in {}.", country)
+			}
+			VideoUnavailable::GeoBlocked(None) => {
+				write!(f, "Video is not avaliable in this region.")
+			}
+			VideoUnavailable::Other(status) => {
+				write!(f, "Video is not avaliable (status {}).", status)
+			}
+		}
+	}
+}
+
+impl error::Error for VideoUnavailable {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		None
+	}
+}
+
+/**
+Determines whether `user` can watch an episode at the time `now`, with None being an anonymous visitor.
+Geo-restrictions aren't checked if the country is None.
+
+Denials are reported in order of precedence: a geo-blocked episode can't be watched by anyone,
+while an episode that is sponsor only stays that way even once it has gone live.
+An anonymous visitor is told to log in when that alone would make the episode available.
+*/
+pub fn can_watch(
+	episode: &Episode,
+	user: Option<&User>,
+	country: Option<Country>,
+	now: DateTime<Utc>,
+) -> Result<(), VideoUnavailable> {
+	let attributes = &episode.attributes;
+
+	if let Some(country) = country {
+		if !episode.is_viewable_in(country) {
+			return Err(VideoUnavailable::GeoBlocked(Some(country)));
+		}
+	}
+
+	// Positive tiers are paid memberships, which the user's own member_tier_i has to reach.
+	let required = attributes.member_tier_i;
+	if required > 0 && required > user.map_or(0, |user| user.attributes.member_tier_i) {
+		return Err(VideoUnavailable::SponsorOnly);
+	}
+
+	let tier = user.map_or(Tier::Public, User::tier);
+
+	match episode.availability_for(tier, now) {
+		Availability::Available => Ok(()),
+		Availability::Upcoming(at) => {
+			// Members often get episodes before the public, so logging in may be enough.
+			if user.is_none()
+				&& episode.availability_for(Tier::Member, now) == Availability::Available
+			{
+				Err(VideoUnavailable::NotLoggedIn)
+			} else {
+				Err(VideoUnavailable::NotYetPublic(Some(at)))
+			}
+		}
+		Availability::Unavailable => Err(VideoUnavailable::SponsorOnly),
+	}
+}
+
+/// Works out why the API refused a video from the response's status and body.
+/// The body is usually a json object with a message, which is searched for the reason.
+pub(crate) fn denial(status: StatusCode, body: &str) -> VideoUnavailable {
+	let message = serde_json::from_str::<serde_json::Value>(body)
+		.ok()
+		.and_then(|json| {
+			["message", "error"]
+				.iter()
+				.find_map(|key| json[key].as_str().map(str::to_string))
+		})
+		.unwrap_or_else(|| body.to_string())
+		.to_lowercase();
+
+	if message.contains("country") || message.contains("region") || message.contains("geo") {
+		VideoUnavailable::GeoBlocked(None)
+	} else if message.contains("sponsor") || message.contains("first member") {
+		VideoUnavailable::SponsorOnly
+	} else if message.contains("not yet") || message.contains("golive") {
+		VideoUnavailable::NotYetPublic(None)
+	} else if status == StatusCode::UNAUTHORIZED {
+		VideoUnavailable::NotLoggedIn
+	} else {
+		VideoUnavailable::Other(status.as_u16())
+	}
+}
@@ -0,0 +1,186 @@
+/*!
+A content-addressed archive of the raw responses `Requests` receives.
+
+The typed structs only keep the fields this crate knows about, so archiving the original bodies is the
+only way to preserve everything the API returned. Each body is stored once under its sha256 hash, and
+an index records the url, status, headers and time of every response.
+
+```text
+archive/
+	index.jsonl
+	objects/
+		3f/
+			3fa4...c2
+```
+
+Typed models can be re-hydrated from the archive later, even with newer versions of this crate.
+
+```no_run
+use roosterteeth_rs::archive::Archive;
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::structs::channels;
+
+let archive = Archive::open("archive").unwrap();
+let requests = Requests::new(Credential::Anonymous).with_archive(archive);
+requests.list_channels();
+
+let archive = Archive::open("archive").unwrap();
+let record = archive.records().unwrap().pop().unwrap();
+let raw: serde_json::Value = archive.load(&record).unwrap();
+let typed: channels::Root = archive.load(&record).unwrap();
+```
+*/
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+const INDEX: &str = "index.jsonl";
+const OBJECTS: &str = "objects";
+
+/// A response stored in the archive, whose body can be read with [body](./struct.Archive.html#method.body).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+	pub method: String,
+	/// The full url of the request, including its query.
+	pub url: String,
+	pub status: u16,
+	pub fetched_at: DateTime<Utc>,
+	/// The response headers, values that aren't valid text are left out.
+	pub headers: BTreeMap<String, String>,
+	/// The sha256 hash of the body, in lowercase hex.
+	pub sha256: String,
+	pub size: u64,
+}
+
+/// An archive in a directory, which is created if it doesn't exist yet.
+#[derive(Debug, Clone)]
+pub struct Archive {
+	dir: PathBuf,
+}
+
+impl Archive {
+	pub fn open<P: AsRef<Path>>(dir: P) -> io::Result<Archive> {
+		let dir = dir.as_ref().to_path_buf();
+		fs::create_dir_all(dir.join(OBJECTS))?;
+
+		Ok(Archive { dir })
+	}
+
+	pub fn dir(&self) -> &Path {
+		&self.dir
+	}
+
+	/// Stores a body, unless an identical one is already archived, and appends its record to the index.
+	pub fn store(
+		&self,
+		method: &str,
+		url: &str,
+		status: u16,
+		headers: BTreeMap<String, String>,
+		body: &[u8],
+	) -> io::Result<Record> {
+		let sha256 = hash(body);
+		let path = self.object_path(&sha256);
+
+		if !path.exists() {
+			fs::create_dir_all(path.parent().unwrap())?;
+
+			// Written to a temporary file first, so a crash never leaves a truncated object behind.
+			let partial = path.with_extension("partial");
+			fs::write(&partial, body)?;
+			fs::rename(&partial, &path)?;
+		}
+
+		let record = Record {
+			method: method.to_string(),
+			url: url.to_string(),
+			status,
+			fetched_at: Utc::now(),
+			headers,
+			sha256,
+			size: body.len() as u64,
+		};
+
+		let mut index = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(self.dir.join(INDEX))?;
+		let mut line = serde_json::to_vec(&record)?;
+		line.push(b'\n');
+		index.write_all(&line)?;
+
+		Ok(record)
+	}
+
+	/// Every record in the order they were archived.
+	pub fn records(&self) -> io::Result<Vec<Record>> {
+		let file = match File::open(self.dir.join(INDEX)) {
+			Ok(file) => file,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+			Err(e) => return Err(e),
+		};
+
+		let mut records = Vec::new();
+		for line in BufReader::new(file).lines() {
+			let line = line?;
+			if !line.trim().is_empty() {
+				records.push(serde_json::from_str(&line)?);
+			}
+		}
+
+		Ok(records)
+	}
+
+	/// The most recent successful response for a url.
+	pub fn latest(&self, url: &str) -> io::Result<Option<Record>> {
+		Ok(self
+			.records()?
+			.into_iter()
+			.rev()
+			.find(|record| record.url == url && (200..300).contains(&record.status)))
+	}
+
+	/// Reads the body of a record, checking that it still matches its hash.
+	pub fn body(&self, record: &Record) -> io::Result<Vec<u8>> {
+		let body = fs::read(self.object_path(&record.sha256))?;
+
+		if hash(&body) != record.sha256 {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				format!("archived body {} is corrupted", record.sha256),
+			));
+		}
+
+		Ok(body)
+	}
+
+	/// Parses the body of a record, either as one of the typed structs or as a `serde_json::Value`.
+	pub fn load<T: DeserializeOwned>(&self, record: &Record) -> io::Result<T> {
+		serde_json::from_slice(&self.body(record)?).map_err(io::Error::from)
+	}
+
+	/// Parses the most recent successful response for a url, or None if it was never archived.
+	pub fn rehydrate<T: DeserializeOwned>(&self, url: &str) -> io::Result<Option<T>> {
+		match self.latest(url)? {
+			Some(record) => self.load(&record).map(Some),
+			None => Ok(None),
+		}
+	}
+
+	fn object_path(&self, sha256: &str) -> PathBuf {
+		self.dir.join(OBJECTS).join(&sha256[..2]).join(sha256)
+	}
+}
+
+fn hash(body: &[u8]) -> String {
+	Sha256::digest(body)
+		.iter()
+		.map(|byte| format!("{:02x}", byte))
+		.collect()
+}
@@ -0,0 +1,46 @@
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const USERNAME_VAR: &str = "RT_USERNAME";
+const PASSWORD_VAR: &str = "RT_PASSWORD";
+
+/// The contents of the credentials file, a json object with the username and password.
+#[derive(Debug, Deserialize)]
+pub struct Login {
+	pub username: String,
+	pub password: String,
+}
+
+/// Finds the user's login, preferring the environment over the credentials file.
+pub fn find(path: Option<&Path>) -> Option<Login> {
+	if let (Ok(username), Ok(password)) = (env::var(USERNAME_VAR), env::var(PASSWORD_VAR)) {
+		return Some(Login { username, password });
+	}
+
+	let path = match path {
+		Some(path) => path.to_path_buf(),
+		None => default_path()?,
+	};
+
+	let contents = fs::read_to_string(&path).ok()?;
+
+	match serde_json::from_str(&contents) {
+		Ok(login) => Some(login),
+		Err(e) => {
+			eprintln!("Unable to parse {}: {}", path.display(), e);
+			None
+		}
+	}
+}
+
+/// `$XDG_CONFIG_HOME/roosterteeth-rs/credentials.json`, falling back to `~/.config`.
+fn default_path() -> Option<PathBuf> {
+	let config = match env::var_os("XDG_CONFIG_HOME") {
+		Some(config) => PathBuf::from(config),
+		None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+	};
+
+	Some(config.join("roosterteeth-rs").join("credentials.json"))
+}
@@ -0,0 +1,537 @@
+use clap::ValueEnum;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use reqwest::blocking::Client;
+use roosterteeth_rs::hls::Variant;
+use roosterteeth_rs::layout::{sanitize, Manifest, Plan};
+use roosterteeth_rs::nfo;
+use roosterteeth_rs::requests::Requests;
+use roosterteeth_rs::structs::episodes::Episode;
+use roosterteeth_rs::structs::ids::{EpisodeSlug, ShowSlug};
+use roosterteeth_rs::structs::seasons::Season;
+use roosterteeth_rs::structs::series::BonusFeature;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+
+use crate::output::{Format, Table};
+
+pub const DEFAULT_TEMPLATE: &str = "{show}/S{season:02}E{number:02} - {title}";
+
+/// What kind of slug is being downloaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Kind {
+	Episode,
+	Season,
+	Show,
+}
+
+/// The HLS variant to download, either the best or worst available or the closest to a height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quality {
+	Best,
+	Worst,
+	Height(u32),
+}
+
+impl FromStr for Quality {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"best" => Ok(Quality::Best),
+			"worst" => Ok(Quality::Worst),
+			_ => s
+				.trim_end_matches('p')
+				.parse()
+				.map(Quality::Height)
+				.map_err(|_| format!("expected best, worst or a height like 720p, got {}", s)),
+		}
+	}
+}
+
+impl Quality {
+	/// Picks a variant from a list sorted from lowest to highest bandwidth.
+	/// A height picks the best variant that isn't taller, or the smallest one if they all are.
+	/// Audio only variants are skipped, unless the playlist has nothing else.
+	fn select<'a>(&self, variants: &'a [Variant]) -> Option<&'a Variant> {
+		let mut video: Vec<&Variant> = variants
+			.iter()
+			.filter(|variant| variant.resolution.is_some())
+			.collect();
+		if video.is_empty() {
+			video = variants.iter().collect();
+		}
+
+		match self {
+			Quality::Best => video.last().copied(),
+			Quality::Worst => video.first().copied(),
+			Quality::Height(height) => video
+				.iter()
+				.rev()
+				.find(|variant| variant.height().is_some_and(|h| h <= *height))
+				.or_else(|| video.first())
+				.copied(),
+		}
+	}
+}
+
+pub struct Options<'a> {
+	pub kind: Kind,
+	pub quality: Quality,
+	pub output: &'a Path,
+	pub template: &'a str,
+	pub list_qualities: bool,
+	pub format: Format,
+	pub nfo: bool,
+	pub plex: bool,
+}
+
+pub fn run(requests: &Requests, slug: &str, options: &Options) {
+	let mut episodes = resolve(requests, slug, options.kind);
+	// Bonus features only have a place in a Plex library, as specials.
+	let bonus = if options.plex && options.kind == Kind::Show {
+		// Bare videos aren't episodes, so there is nothing to name or number them by.
+		requests
+			.get_bonus_features(&requests.get_series(&parse(slug)))
+			.into_iter()
+			.filter_map(BonusFeature::into_episode)
+			.collect()
+	} else {
+		Vec::new()
+	};
+
+	if options.list_qualities {
+		list_qualities(requests, &episodes, options.format);
+		return;
+	}
+
+	let layout = plan_layout(requests, &episodes, &bonus, options);
+	episodes.extend(bonus);
+
+	let client = Client::new();
+	let progress = MultiProgress::new();
+	let overall = progress.add(ProgressBar::new(episodes.len() as u64));
+	overall.set_style(
+		ProgressStyle::with_template("{prefix:>10} [{bar:40}] {pos}/{len}")
+			.unwrap()
+			.progress_chars("=> "),
+	);
+	overall.set_prefix("episodes");
+
+	for episode in &episodes {
+		let path = match layout.episodes.get(&episode.attributes.slug) {
+			Some(path) => with_extension(path, "ts"),
+			None => {
+				progress
+					.println(format!(
+						"Skipping {}, it wasn't laid out",
+						episode.attributes.slug
+					))
+					.unwrap();
+				overall.inc(1);
+				continue;
+			}
+		};
+
+		if path.exists() {
+			progress
+				.println(format!("Skipping {}, already downloaded", path.display()))
+				.unwrap();
+		} else if let Err(e) =
+			download_episode(requests, &client, &progress, episode, &path, options)
+		{
+			progress
+				.println(format!(
+					"Failed to download {}: {}",
+					episode.attributes.slug, e
+				))
+				.unwrap();
+		}
+
+		overall.inc(1);
+	}
+
+	overall.finish();
+
+	if options.nfo {
+		if let Err(e) = write_metadata(requests, &episodes, &layout) {
+			eprintln!("Failed to write the nfo files: {}", e);
+		}
+	}
+}
+
+/// Where each episode is saved without its extension, and the folder of each show.
+#[derive(Default)]
+struct Layout {
+	episodes: HashMap<EpisodeSlug, PathBuf>,
+	shows: HashMap<ShowSlug, PathBuf>,
+}
+
+/// Lays out the episodes using either the template or the Plex naming scheme.
+/// Plex layouts also save a manifest of where each episode went in the show's folder.
+fn plan_layout(
+	requests: &Requests,
+	episodes: &[Episode],
+	bonus: &[Episode],
+	options: &Options,
+) -> Layout {
+	let mut layout = Layout::default();
+
+	// A show can have bonus features without any regular episodes, so both are needed to find the shows.
+	let all: Vec<Episode> = episodes.iter().chain(bonus).cloned().collect();
+	for show in shows(&all) {
+		let show_episodes: Vec<Episode> = episodes
+			.iter()
+			.filter(|episode| &episode.attributes.show_slug == show)
+			.cloned()
+			.collect();
+
+		if options.plex {
+			let series = requests.get_series(show);
+			let mut plan = Plan::new(&series, &show_episodes, bonus);
+
+			let collisions = plan.collisions().len();
+			if collisions > 0 {
+				eprintln!(
+					"{} has {} episode numbers used more than once, numbering its seasons sequentially instead",
+					show, collisions
+				);
+				plan.renumber();
+			}
+
+			// Episodes that were downloaded before keep their paths, even if the API has renumbered them since.
+			let dir = options.output.join(&plan.show_dir);
+			match Manifest::load(dir.join("manifest.json")) {
+				Ok(previous) => plan.keep_paths(&previous),
+				Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+				Err(e) => eprintln!("Ignoring the manifest of {}: {}", show, e),
+			}
+
+			// Kept paths can clash with the numbers given to new episodes, which the manifest records.
+			let manifest = plan.manifest();
+			if !manifest.collisions.is_empty() {
+				eprintln!(
+					"{} has {} episode numbers used more than once after keeping the paths of its manifest",
+					show,
+					manifest.collisions.len()
+				);
+			}
+			if let Err(e) =
+				fs::create_dir_all(&dir).and_then(|_| manifest.save(dir.join("manifest.json")))
+			{
+				eprintln!("Failed to write the manifest of {}: {}", show, e);
+			}
+
+			for entry in &plan.entries {
+				layout.episodes.insert(
+					entry.episode.attributes.slug.clone(),
+					options.output.join(&entry.path),
+				);
+			}
+			layout.shows.insert(show.clone(), dir);
+		} else {
+			for episode in &show_episodes {
+				layout.episodes.insert(
+					episode.attributes.slug.clone(),
+					options
+						.output
+						.join(render_template(options.template, episode)),
+				);
+			}
+
+			// The show's folder is the part of the template up to and including the show's name.
+			let components: Vec<&str> = options.template.split('/').collect();
+			let dir = match components[..components.len() - 1]
+				.iter()
+				.position(|component| component.contains("{show"))
+			{
+				Some(last) => options.output.join(render_template(
+					&components[..=last].join("/"),
+					&show_episodes[0],
+				)),
+				None => options.output.to_path_buf(),
+			};
+			layout.shows.insert(show.clone(), dir);
+		}
+	}
+
+	layout
+}
+
+/// The shows a list of episodes belong to, in order.
+fn shows(episodes: &[Episode]) -> Vec<&ShowSlug> {
+	let mut shows: Vec<&ShowSlug> = Vec::new();
+
+	for episode in episodes {
+		if !shows.contains(&&episode.attributes.show_slug) {
+			shows.push(&episode.attributes.show_slug);
+		}
+	}
+
+	shows
+}
+
+fn with_extension(path: &Path, extension: &str) -> PathBuf {
+	let mut path = path.as_os_str().to_owned();
+	path.push(".");
+	path.push(extension);
+
+	PathBuf::from(path)
+}
+
+/// Writes the nfo files and artwork of each show the episodes belong to.
+fn write_metadata(requests: &Requests, episodes: &[Episode], layout: &Layout) -> io::Result<()> {
+	let channels = requests.list_channels();
+
+	for show in shows(episodes) {
+		let episodes: Vec<Episode> = episodes
+			.iter()
+			.filter(|episode| &episode.attributes.show_slug == show)
+			.cloned()
+			.collect();
+
+		let series = requests.get_series(show);
+		let seasons: Vec<Season> = requests
+			.get_seasons(show, Some("asc"))
+			.into_iter()
+			.filter(|season| {
+				episodes
+					.iter()
+					.any(|episode| episode.attributes.season_slug == season.attributes.slug)
+			})
+			.collect();
+		let channel = channels
+			.iter()
+			.find(|channel| channel.attributes.slug == series.attributes.channel_slug);
+
+		nfo::export(
+			requests,
+			&layout.shows[show],
+			&series,
+			&seasons,
+			&episodes,
+			channel,
+			|episode| layout.episodes[&episode.attributes.slug].clone(),
+		)?;
+	}
+
+	Ok(())
+}
+
+/// Expands a slug into the list of episodes it refers to.
+fn resolve(requests: &Requests, slug: &str, kind: Kind) -> Vec<Episode> {
+	match kind {
+		Kind::Episode => vec![requests.get_episode(&parse(slug))],
+		Kind::Season => requests
+			.iter_season_episodes(&parse(slug), Some("asc"))
+			.collect(),
+		Kind::Show => requests
+			.get_seasons(&parse(slug), Some("asc"))
+			.iter()
+			.flat_map(|season| requests.iter_season_episodes(&season.attributes.slug, Some("asc")))
+			.collect(),
+	}
+}
+
+fn parse<T: FromStr>(slug: &str) -> T
+where
+	T::Err: std::fmt::Display,
+{
+	slug.parse().unwrap_or_else(|e| {
+		eprintln!("{}", e);
+		process::exit(2);
+	})
+}
+
+fn list_qualities(requests: &Requests, episodes: &[Episode], format: Format) {
+	let mut table = Table::new(vec!["slug", "resolution", "bandwidth", "codecs"]);
+
+	for episode in episodes {
+		let slug = &episode.attributes.slug;
+
+		let video = match requests.get_video(slug) {
+			Ok(video) => video,
+			Err(e) => {
+				eprintln!("{}: {}", slug, e);
+				continue;
+			}
+		};
+
+		for variant in requests.get_hls_variants(&video) {
+			table.push(vec![
+				slug.to_string().into(),
+				variant
+					.resolution
+					.map(|(width, height)| format!("{}x{}", width, height))
+					.into(),
+				variant.bandwidth.into(),
+				variant.codecs.into(),
+			]);
+		}
+	}
+
+	table.print(format);
+}
+
+fn download_episode(
+	requests: &Requests,
+	client: &Client,
+	progress: &MultiProgress,
+	episode: &Episode,
+	path: &Path,
+	options: &Options,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let video = requests.get_video(&episode.attributes.slug)?;
+	let variants = requests.get_hls_variants(&video);
+	let variant = options
+		.quality
+		.select(&variants)
+		.ok_or("the video has no streams")?;
+	let segments = requests.get_hls_segments(variant);
+
+	if let Some(parent) = path.parent() {
+		fs::create_dir_all(parent)?;
+	}
+
+	let bar = progress.add(ProgressBar::new(segments.len() as u64));
+	bar.set_style(
+		ProgressStyle::with_template("{prefix:>10} [{bar:40}] {pos}/{len} {msg}")
+			.unwrap()
+			.progress_chars("=> "),
+	);
+	bar.set_prefix(
+		variant
+			.height()
+			.map(|height| format!("{}p", height))
+			.unwrap_or_else(|| String::from("audio")),
+	);
+	bar.set_message(episode.attributes.title.clone());
+
+	// Download into a temporary file, so an interrupted download isn't mistaken for a finished one.
+	let partial = partial_path(path);
+	let mut file = File::create(&partial)?;
+	for segment in &segments {
+		let mut response = client.get(segment).send()?.error_for_status()?;
+		io::copy(&mut response, &mut file)?;
+		bar.inc(1);
+	}
+	file.flush()?;
+	fs::rename(&partial, path)?;
+
+	bar.finish_and_clear();
+	progress.println(format!("Downloaded {}", path.display()))?;
+
+	Ok(())
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+	with_extension(path, "part")
+}
+
+/// Fills in a template such as `{show}/S{season:02}E{number:02} - {title}`.
+/// Numbers can be zero padded with `:0N`, and values are stripped of characters that aren't valid in file names.
+pub fn render_template(template: &str, episode: &Episode) -> String {
+	let attributes = &episode.attributes;
+	let mut rendered = String::new();
+	let mut rest = template;
+
+	while let Some(start) = rest.find('{') {
+		rendered.push_str(&rest[..start]);
+
+		let end = match rest[start..].find('}') {
+			Some(end) => start + end,
+			// An unclosed brace is kept as it is.
+			None => {
+				rendered.push_str(&rest[start..]);
+				return rendered;
+			}
+		};
+
+		let placeholder = &rest[start + 1..end];
+		let (key, width) = match placeholder.split_once(':') {
+			Some((key, format)) => (key, format.trim_start_matches('0').parse().unwrap_or(0)),
+			None => (placeholder, 0),
+		};
+
+		let value = match key {
+			"show" => sanitize(&attributes.show_title),
+			"show_slug" => attributes.show_slug.to_string(),
+			"channel" => attributes.channel_slug.to_string(),
+			"season" => format!("{:0width$}", attributes.season_number, width = width),
+			"number" => format!("{:0width$}", attributes.number, width = width),
+			"title" => sanitize(&attributes.title),
+			"slug" => attributes.slug.to_string(),
+			_ => format!("{{{}}}", placeholder),
+		};
+		rendered.push_str(&value);
+
+		rest = &rest[end + 1..];
+	}
+	rendered.push_str(rest);
+
+	rendered
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use roosterteeth_rs::structs::episodes;
+
+	fn episode() -> Episode {
+		let root: episodes::Root =
+			serde_json::from_str(include_str!("../../../fixtures/episodes.json")).unwrap();
+
+		root.data.into_iter().next().unwrap()
+	}
+
+	#[test]
+	fn render_default_template() {
+		assert_eq!(
+			render_template(DEFAULT_TEMPLATE, &episode()),
+			"Red vs. Blue/S01E01 - Episode 1_ Why Are We Here_"
+		);
+	}
+
+	#[test]
+	fn render_unusual_templates() {
+		let episode = episode();
+
+		assert_eq!(render_template("abc{def", &episode), "abc{def");
+		assert_eq!(
+			render_template("{slug}/{season:03}{", &episode),
+			"red-vs-blue-season-1-episode-1/001{"
+		);
+		assert_eq!(
+			render_template("{unknown} {number}", &episode),
+			"{unknown} 1"
+		);
+		assert_eq!(
+			render_template("no placeholders", &episode),
+			"no placeholders"
+		);
+	}
+
+	#[test]
+	fn select_skips_audio_only_variants() {
+		let variant = |bandwidth, resolution| Variant {
+			bandwidth,
+			resolution,
+			codecs: None,
+			url: format!("{}.m3u8", bandwidth),
+		};
+		let variants = vec![
+			variant(64_000, None),
+			variant(800_000, Some((640, 360))),
+			variant(5_000_000, Some((1920, 1080))),
+		];
+
+		assert_eq!(Quality::Worst.select(&variants), Some(&variants[1]));
+		assert_eq!(Quality::Height(240).select(&variants), Some(&variants[1]));
+		assert_eq!(Quality::Height(720).select(&variants), Some(&variants[1]));
+		assert_eq!(Quality::Best.select(&variants), Some(&variants[2]));
+		assert_eq!(Quality::Worst.select(&variants[..1]), Some(&variants[0]));
+	}
+}
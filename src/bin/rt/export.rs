@@ -0,0 +1,101 @@
+use clap::ValueEnum;
+use roosterteeth_rs::export::{self, Csv, Flatten, JsonLines, Nested, Writer};
+use roosterteeth_rs::requests::Requests;
+use roosterteeth_rs::structs::channels::Channel;
+use roosterteeth_rs::structs::episodes::Episode;
+use roosterteeth_rs::structs::ids::ChannelSlug;
+use roosterteeth_rs::structs::seasons::Season;
+use roosterteeth_rs::structs::series::Series;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process;
+
+/// What is being exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Kind {
+	Channels,
+	Shows,
+	Seasons,
+	Episodes,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+	Jsonl,
+	Csv,
+	/// Requires building with the parquet feature.
+	#[cfg(feature = "parquet")]
+	Parquet,
+}
+
+pub struct Options<'a> {
+	pub kind: Kind,
+	pub output: Option<&'a Path>,
+	pub format: Format,
+	pub explode: bool,
+	pub channel: Option<&'a ChannelSlug>,
+}
+
+pub fn run(requests: &Requests, options: &Options) {
+	let output: Box<dyn Write + Send> = match options.output {
+		Some(path) => match File::create(path) {
+			Ok(file) => Box::new(BufWriter::new(file)),
+			Err(e) => {
+				eprintln!("{}: {}", path.display(), e);
+				process::exit(1);
+			}
+		},
+		None => Box::new(io::stdout()),
+	};
+
+	let mut writer: Box<dyn Writer> = match options.format {
+		Format::Jsonl => Box::new(JsonLines::new(output)),
+		Format::Csv => Box::new(Csv::new(output)),
+		#[cfg(feature = "parquet")]
+		Format::Parquet => Box::new(export::Parquet::new(output)),
+	};
+
+	let result = match options.kind {
+		Kind::Channels => write::<Channel, _>(requests.list_channels(), options, &mut writer),
+		Kind::Shows => write::<Series, _>(
+			requests.iter_series(options.channel, Some("asc")),
+			options,
+			&mut writer,
+		),
+		Kind::Seasons => write::<Season, _>(
+			requests
+				.iter_series(options.channel, Some("asc"))
+				.flat_map(|series| requests.get_seasons(&series.attributes.slug, Some("asc"))),
+			options,
+			&mut writer,
+		),
+		Kind::Episodes => write::<Episode, _>(
+			requests.iter_episodes(options.channel, Some("asc")),
+			options,
+			&mut writer,
+		),
+	};
+
+	match result {
+		Ok(rows) => eprintln!("Exported {} rows", rows),
+		Err(e) => {
+			eprintln!("export failed: {}", e);
+			process::exit(1);
+		}
+	}
+}
+
+fn write<T, I>(items: I, options: &Options, writer: &mut Box<dyn Writer>) -> io::Result<usize>
+where
+	T: Flatten,
+	I: IntoIterator<Item = T>,
+{
+	let nested = if options.explode {
+		Nested::Exploded
+	} else {
+		Nested::Joined
+	};
+
+	export::write::<T, _, _>(items, nested, writer.as_mut())
+}
@@ -0,0 +1,428 @@
+//! `rt` is a small command line interface for browsing the RoosterTeeth catalog.
+//!
+//! Logging in is only required to look up and download videos. The username and password are read from the
+//! `RT_USERNAME` and `RT_PASSWORD` environment variables, or from a credentials file containing
+//! `{"username": "...", "password": "..."}`.
+
+mod credentials;
+mod download;
+mod export;
+mod output;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use roosterteeth_rs::archive::Archive;
+use roosterteeth_rs::catalog::Catalog;
+use roosterteeth_rs::geo::Country;
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::structs::episodes::Episode;
+use roosterteeth_rs::structs::ids::{ChannelSlug, EpisodeSlug, SeasonSlug, ShowSlug};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::download::Quality;
+use crate::output::{Format, Table};
+
+#[derive(Debug, Parser)]
+#[command(name = "rt", version, about = "Browse the RoosterTeeth catalog")]
+struct Cli {
+	/// How results are printed.
+	#[arg(short, long, value_enum, default_value_t = Format::Table, global = true)]
+	format: Format,
+
+	/// Path to a json credentials file, defaults to ~/.config/roosterteeth-rs/credentials.json
+	#[arg(long, global = true, env = "RT_CREDENTIALS")]
+	credentials: Option<PathBuf>,
+
+	/// Only list episodes and shows that can be watched in this country, as a two letter code.
+	#[arg(long, global = true, env = "RT_COUNTRY")]
+	country: Option<Country>,
+
+	/// Keeps the raw json of every response in this directory.
+	#[arg(long, global = true, env = "RT_ARCHIVE")]
+	archive: Option<PathBuf>,
+
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+	/// Lists all channels.
+	Channels,
+
+	/// Lists all shows.
+	Shows {
+		/// Only list shows from this channel slug.
+		#[arg(long)]
+		channel: Option<ChannelSlug>,
+		#[arg(long, value_enum)]
+		order: Option<Order>,
+	},
+
+	/// Lists the seasons of a show.
+	Seasons {
+		/// The show slug, for example red-vs-blue
+		show: ShowSlug,
+		#[arg(long, value_enum)]
+		order: Option<Order>,
+	},
+
+	/// Lists the episodes of a season, or the latest episodes if no season is given.
+	Episodes {
+		/// The season slug, for example red-vs-blue-season-1
+		season: Option<SeasonSlug>,
+		/// Only list episodes from this channel slug, ignored when a season is given.
+		#[arg(long)]
+		channel: Option<ChannelSlug>,
+		/// The page of latest episodes, ignored when a season is given.
+		#[arg(long, default_value_t = 1)]
+		page: u16,
+		#[arg(long, value_enum)]
+		order: Option<Order>,
+	},
+
+	/// Shows a single episode.
+	Episode {
+		/// The episode slug, for example red-vs-blue-season-1-episode-1
+		slug: EpisodeSlug,
+	},
+
+	/// Shows the video streams of an episode, this may require logging in.
+	Video {
+		/// The episode slug, for example red-vs-blue-season-1-episode-1
+		slug: EpisodeSlug,
+	},
+
+	/// Downloads an episode, or every episode of a season or show, this may require logging in.
+	Download {
+		/// The episode, season or show slug.
+		slug: String,
+		/// What kind of slug is being downloaded.
+		#[arg(long, value_enum, default_value_t = download::Kind::Episode)]
+		kind: download::Kind,
+		/// best, worst, or the maximum height of the video, for example 720p.
+		#[arg(short, long, default_value = "best")]
+		quality: Quality,
+		/// The directory downloads are saved to.
+		#[arg(short, long, default_value = ".")]
+		output: PathBuf,
+		/// The file name of each episode, relative to the output directory. Valid placeholders are
+		/// {show}, {show_slug}, {channel}, {season}, {number}, {title} and {slug}.
+		#[arg(short, long, default_value = download::DEFAULT_TEMPLATE)]
+		template: String,
+		/// Lists the available qualities instead of downloading.
+		#[arg(long)]
+		list_qualities: bool,
+		/// Also writes Kodi/Jellyfin nfo files and artwork for the downloaded episodes and their show.
+		#[arg(long)]
+		nfo: bool,
+		/// Names files the way Plex expects instead of using the template, with a show's bonus features
+		/// as specials, and saves a manifest of where each episode went.
+		#[arg(long)]
+		plex: bool,
+	},
+
+	/// Exports every channel, show, season or episode to a file, fetching a page at a time.
+	Export {
+		#[arg(value_enum)]
+		kind: export::Kind,
+		/// The file to write, or standard output if not given.
+		#[arg(short, long)]
+		output: Option<PathBuf>,
+		/// The format of the file.
+		#[arg(long = "as", value_enum, default_value_t = export::Format::Jsonl)]
+		format: export::Format,
+		/// Writes a row per tag, genre, cast member and blacklisted country instead of joining them.
+		#[arg(long)]
+		explode: bool,
+		/// Only export shows and episodes from this channel slug.
+		#[arg(long)]
+		channel: Option<ChannelSlug>,
+	},
+
+	/// Saves a snapshot of the catalog, which can later be compared with diff.
+	Sync {
+		/// The json file the catalog is saved to.
+		output: PathBuf,
+		/// Only sync shows from this channel slug.
+		#[arg(long)]
+		channel: Option<ChannelSlug>,
+	},
+
+	/// Serves a saved catalog with the same json as the RoosterTeeth api, requires the serve feature.
+	#[cfg(feature = "serve")]
+	Serve {
+		/// The json file saved by sync.
+		catalog: PathBuf,
+		#[arg(long, default_value = "127.0.0.1:8080")]
+		address: String,
+	},
+
+	/// Compares two saved catalogs, printed as text or with --format json.
+	Diff {
+		/// The older catalog.
+		old: PathBuf,
+		/// The newer catalog.
+		new: PathBuf,
+	},
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Order {
+	Asc,
+	Desc,
+}
+
+impl Order {
+	fn as_str(self) -> &'static str {
+		match self {
+			Order::Asc => "asc",
+			Order::Desc => "desc",
+		}
+	}
+}
+
+fn main() {
+	let cli = Cli::parse();
+
+	match &cli.command {
+		Command::Channels => {
+			let requests = anonymous(&cli);
+
+			let mut table = Table::new(vec!["slug", "name", "id"]);
+			for channel in requests.list_channels() {
+				table.push(vec![
+					channel.attributes.slug.to_string().into(),
+					channel.attributes.name.into(),
+					channel.id.0.into(),
+				]);
+			}
+			table.print(cli.format);
+		}
+		Command::Shows { channel, order } => {
+			let requests = anonymous(&cli);
+			let shows = requests.list_series(channel.as_ref(), order.map(Order::as_str));
+
+			let mut table = Table::new(vec!["slug", "title", "channel", "seasons", "episodes"]);
+			for show in shows {
+				table.push(vec![
+					show.attributes.slug.to_string().into(),
+					show.attributes.title.into(),
+					show.attributes.channel_slug.to_string().into(),
+					show.attributes.season_count.into(),
+					show.attributes.episode_count.into(),
+				]);
+			}
+			table.print(cli.format);
+		}
+		Command::Seasons { show, order } => {
+			let requests = anonymous(&cli);
+			let seasons = requests.get_seasons(show, order.map(Order::as_str));
+
+			let mut table = Table::new(vec!["slug", "number", "title", "published"]);
+			for season in seasons {
+				table.push(vec![
+					season.attributes.slug.to_string().into(),
+					season.attributes.number.into(),
+					season.attributes.title.into(),
+					season.attributes.published_at.to_rfc3339().into(),
+				]);
+			}
+			table.print(cli.format);
+		}
+		Command::Episodes {
+			season,
+			channel,
+			page,
+			order,
+		} => {
+			let requests = anonymous(&cli);
+			let order = order.map(Order::as_str);
+			let episodes = match season {
+				Some(season) => requests.get_season_episodes(season, order),
+				None => requests.list_episodes(*page, channel.as_ref(), order),
+			};
+
+			episode_table(episodes).print(cli.format);
+		}
+		Command::Episode { slug } => {
+			let requests = anonymous(&cli);
+
+			episode_table(vec![requests.get_episode(slug)]).print(cli.format);
+		}
+		Command::Video { slug } => {
+			let requests = authenticated(&cli);
+
+			let video = match requests.get_video(slug) {
+				Ok(video) => video,
+				Err(e) => {
+					eprintln!("{}: {}", slug, e);
+					process::exit(1);
+				}
+			};
+
+			let mut table = Table::new(vec!["slug", "media_type", "member_tier", "url"]);
+			table.push(vec![
+				video.attributes.content_slug.to_string().into(),
+				video.attributes.media_type.into(),
+				video.attributes.member_tier.into(),
+				video.attributes.url.into(),
+			]);
+			table.print(cli.format);
+		}
+		Command::Download {
+			slug,
+			kind,
+			quality,
+			output,
+			template,
+			list_qualities,
+			nfo,
+			plex,
+		} => {
+			let requests = authenticated(&cli);
+
+			download::run(
+				&requests,
+				slug,
+				&download::Options {
+					kind: *kind,
+					quality: *quality,
+					output,
+					template,
+					list_qualities: *list_qualities,
+					format: cli.format,
+					nfo: *nfo,
+					plex: *plex,
+				},
+			);
+		}
+		Command::Export {
+			kind,
+			output,
+			format,
+			explode,
+			channel,
+		} => {
+			let requests = anonymous(&cli);
+
+			export::run(
+				&requests,
+				&export::Options {
+					kind: *kind,
+					output: output.as_deref(),
+					format: *format,
+					explode: *explode,
+					channel: channel.as_ref(),
+				},
+			);
+		}
+		Command::Sync { output, channel } => {
+			let catalog = anonymous(&cli).sync_catalog(channel.as_ref());
+
+			if let Err(e) = catalog.save(output) {
+				eprintln!("{}: {}", output.display(), e);
+				process::exit(1);
+			}
+			eprintln!(
+				"Saved {} shows and {} episodes",
+				catalog.series.len(),
+				catalog.episodes.len()
+			);
+		}
+		#[cfg(feature = "serve")]
+		Command::Serve { catalog, address } => {
+			let server = match roosterteeth_rs::serve::Server::bind(address, load_catalog(catalog))
+			{
+				Ok(server) => server,
+				Err(e) => {
+					eprintln!("{}: {}", address, e);
+					process::exit(1);
+				}
+			};
+
+			eprintln!("Serving {} at {}", catalog.display(), server.api_url());
+			server.run();
+		}
+		Command::Diff { old, new } => {
+			let diff = load_catalog(old).diff(&load_catalog(new));
+
+			match cli.format {
+				Format::Json => println!("{}", serde_json::to_string_pretty(&diff).unwrap()),
+				_ => print!("{}", diff),
+			}
+		}
+	}
+}
+
+/// Creates a client that is logged in if the user has provided their credentials.
+fn authenticated(cli: &Cli) -> Requests {
+	let requests = match credentials::find(cli.credentials.as_deref()) {
+		Some(login) => Requests::new(Credential::Login(&login.username, &login.password)),
+		None => Requests::new(Credential::Anonymous),
+	};
+
+	configure(cli, requests)
+}
+
+fn anonymous(cli: &Cli) -> Requests {
+	configure(cli, Requests::new(Credential::Anonymous))
+}
+
+fn configure(cli: &Cli, mut requests: Requests) -> Requests {
+	if let Some(country) = cli.country {
+		requests = requests.with_country(country);
+	}
+
+	if let Some(dir) = &cli.archive {
+		match Archive::open(dir) {
+			Ok(archive) => requests = requests.with_archive(archive),
+			Err(e) => {
+				eprintln!("{}: {}", dir.display(), e);
+				process::exit(1);
+			}
+		}
+	}
+
+	requests
+}
+
+fn load_catalog(path: &Path) -> Catalog {
+	match Catalog::load(path) {
+		Ok(catalog) => catalog,
+		Err(e) => {
+			eprintln!("{}: {}", path.display(), e);
+			process::exit(1);
+		}
+	}
+}
+
+fn episode_table(episodes: Vec<Episode>) -> Table {
+	let mut table = Table::new(vec![
+		"slug",
+		"show",
+		"season",
+		"number",
+		"title",
+		"length",
+		"public_golive_at",
+		"sponsors_only",
+	]);
+
+	for episode in episodes {
+		let attributes = episode.attributes;
+
+		table.push(vec![
+			attributes.slug.to_string().into(),
+			attributes.show_slug.to_string().into(),
+			attributes.season_number.into(),
+			attributes.number.into(),
+			attributes.title.into(),
+			attributes.length.into(),
+			attributes.public_golive_at.to_rfc3339().into(),
+			attributes.is_sponsors_only.into(),
+		]);
+	}
+
+	table
+}
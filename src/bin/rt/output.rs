@@ -0,0 +1,115 @@
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+	Table,
+	Json,
+	Csv,
+}
+
+/// Rows of values that can be printed in any of the output formats.
+pub struct Table {
+	headers: Vec<&'static str>,
+	rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+	pub fn new(headers: Vec<&'static str>) -> Self {
+		Table {
+			headers,
+			rows: Vec::new(),
+		}
+	}
+
+	pub fn push(&mut self, row: Vec<Value>) {
+		debug_assert_eq!(row.len(), self.headers.len());
+
+		self.rows.push(row);
+	}
+
+	pub fn print(&self, format: Format) {
+		match format {
+			Format::Table => self.print_table(),
+			Format::Json => self.print_json(),
+			Format::Csv => self.print_csv(),
+		}
+	}
+
+	/// The text and csv formats print strings without quotes and nulls as empty cells.
+	fn text_rows(&self) -> Vec<Vec<String>> {
+		self.rows
+			.iter()
+			.map(|row| {
+				row.iter()
+					.map(|cell| match cell {
+						Value::Null => String::new(),
+						Value::String(text) => text.clone(),
+						value => value.to_string(),
+					})
+					.collect()
+			})
+			.collect()
+	}
+
+	fn print_table(&self) {
+		let rows = self.text_rows();
+
+		let mut widths: Vec<usize> = self.headers.iter().map(|h| h.len()).collect();
+		for row in &rows {
+			for (width, cell) in widths.iter_mut().zip(row) {
+				*width = (*width).max(cell.chars().count());
+			}
+		}
+
+		let format_row = |cells: Vec<&str>| -> String {
+			cells
+				.iter()
+				.zip(&widths)
+				.map(|(cell, width)| format!("{:width$}", cell, width = width))
+				.collect::<Vec<String>>()
+				.join("  ")
+				.trim_end()
+				.to_string()
+		};
+
+		println!("{}", format_row(self.headers.clone()));
+		for row in &rows {
+			println!(
+				"{}",
+				format_row(row.iter().map(|cell| cell.as_str()).collect())
+			);
+		}
+	}
+
+	fn print_json(&self) {
+		let rows: Vec<Value> = self
+			.rows
+			.iter()
+			.map(|row| {
+				let object: Map<String, Value> = self
+					.headers
+					.iter()
+					.zip(row)
+					.map(|(header, cell)| (header.to_string(), cell.clone()))
+					.collect();
+
+				Value::Object(object)
+			})
+			.collect();
+
+		println!("{}", serde_json::to_string_pretty(&rows).unwrap());
+	}
+
+	fn print_csv(&self) {
+		let mut writer = csv::Writer::from_writer(io::stdout());
+
+		writer.write_record(&self.headers).unwrap();
+		for row in self.text_rows() {
+			writer.write_record(row).unwrap();
+		}
+
+		writer.flush().unwrap();
+	}
+}
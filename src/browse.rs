@@ -0,0 +1,136 @@
+/*!
+Browsing a synced [Catalog](../catalog/struct.Catalog.html) by the tags and cast members included with each episode.
+
+```no_run
+use roosterteeth_rs::catalog::Catalog;
+
+let catalog = Catalog::load("catalog.json").unwrap();
+
+for (tag, count) in catalog.tags() {
+	println!("{} ({} episodes)", tag.attributes.tag, count);
+}
+
+let burnie = catalog.cast_members().into_iter().find(|(member, _)| member.attributes.name == "Burnie Burns");
+if let Some(filmography) = burnie.and_then(|(member, _)| catalog.filmography(member.id)) {
+	for credit in filmography.credits {
+		println!("{}: {} episodes", credit.show_title, credit.episodes.len());
+	}
+}
+```
+*/
+
+use std::collections::BTreeMap;
+
+use crate::catalog::Catalog;
+use crate::structs::episodes::{CastMember, Episode, Tag};
+use crate::structs::ids::{CastMemberId, ShowSlug, TagSlug};
+
+/// The episodes of a single show a cast member appears in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Credit<'a> {
+	pub show_slug: &'a ShowSlug,
+	pub show_title: &'a str,
+	pub episodes: Vec<&'a Episode>,
+}
+
+/// Every appearance of a cast member in the catalog, grouped by show.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filmography<'a> {
+	pub member: &'a CastMember,
+	/// Sorted by show title.
+	pub credits: Vec<Credit<'a>>,
+}
+
+impl<'a> Filmography<'a> {
+	pub fn episode_count(&self) -> usize {
+		self.credits
+			.iter()
+			.map(|credit| credit.episodes.len())
+			.sum()
+	}
+}
+
+impl Catalog {
+	/// The episodes tagged with a tag, in catalog order.
+	pub fn episodes_tagged<'a>(&'a self, tag: &'a TagSlug) -> impl Iterator<Item = &'a Episode> {
+		self.episodes.iter().filter(move |episode| {
+			episode
+				.included
+				.tags
+				.iter()
+				.any(|t| &t.attributes.slug == tag)
+		})
+	}
+
+	/// The episodes a cast member appears in, in catalog order.
+	pub fn episodes_with(&self, member: CastMemberId) -> impl Iterator<Item = &Episode> {
+		self.episodes
+			.iter()
+			.filter(move |episode| episode.included.cast_members.iter().any(|m| m.id == member))
+	}
+
+	/// Every tag used in the catalog along with the number of episodes using it, sorted by slug.
+	pub fn tags(&self) -> Vec<(&Tag, usize)> {
+		let mut tags: BTreeMap<&TagSlug, (&Tag, usize)> = BTreeMap::new();
+
+		for tag in self
+			.episodes
+			.iter()
+			.flat_map(|episode| &episode.included.tags)
+		{
+			tags.entry(&tag.attributes.slug).or_insert((tag, 0)).1 += 1;
+		}
+
+		tags.into_values().collect()
+	}
+
+	/// Every cast member in the catalog along with the number of episodes they appear in, sorted by name.
+	pub fn cast_members(&self) -> Vec<(&CastMember, usize)> {
+		let mut members: BTreeMap<CastMemberId, (&CastMember, usize)> = BTreeMap::new();
+
+		for member in self
+			.episodes
+			.iter()
+			.flat_map(|episode| &episode.included.cast_members)
+		{
+			members.entry(member.id).or_insert((member, 0)).1 += 1;
+		}
+
+		let mut members: Vec<(&CastMember, usize)> = members.into_values().collect();
+		members.sort_by(|(a, _), (b, _)| a.attributes.name.cmp(&b.attributes.name));
+
+		members
+	}
+
+	/// Aggregates the episodes a cast member appears in by show, or None if they aren't in the catalog.
+	pub fn filmography(&self, member: CastMemberId) -> Option<Filmography<'_>> {
+		let mut found = None;
+		let mut credits: BTreeMap<&ShowSlug, Credit> = BTreeMap::new();
+
+		for episode in self.episodes_with(member) {
+			if found.is_none() {
+				found = episode
+					.included
+					.cast_members
+					.iter()
+					.find(|m| m.id == member);
+			}
+
+			let attributes = &episode.attributes;
+			credits
+				.entry(&attributes.show_slug)
+				.or_insert_with(|| Credit {
+					show_slug: &attributes.show_slug,
+					show_title: &attributes.show_title,
+					episodes: Vec::new(),
+				})
+				.episodes
+				.push(episode);
+		}
+
+		let mut credits: Vec<Credit> = credits.into_values().collect();
+		credits.sort_by(|a, b| a.show_title.cmp(b.show_title));
+
+		found.map(|member| Filmography { member, credits })
+	}
+}
@@ -0,0 +1,134 @@
+/*!
+Exports a [Schedule](../schedule/struct.Schedule.html) as an [RFC 5545](https://tools.ietf.org/html/rfc5545)
+iCalendar file, which can be subscribed to from most calendar applications.
+
+Every go-live gets its own event, so an episode that releases to sponsors a week before the public
+shows up twice. The event UID is derived from the episode uuid and tier, which keeps it stable between exports.
+
+```no_run
+use chrono::Utc;
+use roosterteeth_rs::calendar;
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::schedule::Schedule;
+
+let requests = Requests::new(Credential::Anonymous);
+let episodes = requests.list_episodes(1, Some(&"rooster-teeth".parse().unwrap()), None);
+let schedule = Schedule::upcoming(&episodes, Utc::now());
+
+std::fs::write("rooster-teeth.ics", calendar::to_ics("Rooster Teeth", &schedule, Utc::now())).unwrap();
+```
+*/
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::schedule::{GoLive, Schedule};
+
+const PRODID: &str = "-//candunc//roosterteeth-rs//EN";
+const WEBSITE_URL: &str = "https://roosterteeth.com";
+
+// RFC 5545 section 3.1, lines should not be longer than 75 octets excluding the line break.
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Renders every event in the schedule into an iCalendar document named `name`.
+/// `dtstamp` is recorded as the time each event was generated, usually this is `Utc::now()`.
+pub fn to_ics(name: &str, schedule: &Schedule, dtstamp: DateTime<Utc>) -> String {
+	let mut ics = String::new();
+
+	push_line(&mut ics, "BEGIN:VCALENDAR");
+	push_line(&mut ics, "VERSION:2.0");
+	push_line(&mut ics, &format!("PRODID:{}", PRODID));
+	push_line(&mut ics, "CALSCALE:GREGORIAN");
+	push_line(&mut ics, "METHOD:PUBLISH");
+	push_line(&mut ics, &format!("X-WR-CALNAME:{}", escape_text(name)));
+
+	for event in schedule.events() {
+		push_event(&mut ics, event, dtstamp);
+	}
+
+	push_line(&mut ics, "END:VCALENDAR");
+
+	ics
+}
+
+fn push_event(ics: &mut String, event: &GoLive, dtstamp: DateTime<Utc>) {
+	let attributes = &event.episode.attributes;
+	let start = event.at.with_timezone(&Utc);
+	let end = start + Duration::seconds(attributes.length as i64);
+
+	let description = if attributes.caption.is_empty() {
+		attributes.description.clone()
+	} else {
+		format!("{}\n\n{}", attributes.caption, attributes.description)
+	};
+
+	push_line(ics, "BEGIN:VEVENT");
+	push_line(
+		ics,
+		&format!("UID:{}-{}@roosterteeth.com", event.episode.uuid, event.tier),
+	);
+	push_line(ics, &format!("DTSTAMP:{}", format_time(dtstamp)));
+	push_line(ics, &format!("DTSTART:{}", format_time(start)));
+	push_line(ics, &format!("DTEND:{}", format_time(end)));
+	push_line(
+		ics,
+		&format!(
+			"SUMMARY:{}",
+			escape_text(&format!(
+				"{} - {} ({})",
+				attributes.show_title, attributes.title, event.tier
+			))
+		),
+	);
+	push_line(ics, &format!("DESCRIPTION:{}", escape_text(&description)));
+	push_line(
+		ics,
+		&format!(
+			"URL:{}{}",
+			WEBSITE_URL, event.episode.canonical_links.reference
+		),
+	);
+	push_line(ics, &format!("CATEGORIES:{}", event.tier));
+	push_line(ics, "TRANSP:TRANSPARENT");
+	push_line(ics, "END:VEVENT");
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+	time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes a TEXT value as described in RFC 5545 section 3.3.11.
+fn escape_text(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+
+	for c in text.chars() {
+		match c {
+			'\\' => escaped.push_str("\\\\"),
+			';' => escaped.push_str("\\;"),
+			',' => escaped.push_str("\\,"),
+			'\n' => escaped.push_str("\\n"),
+			'\r' => {}
+			_ => escaped.push(c),
+		}
+	}
+
+	escaped
+}
+
+/// Appends a content line, folding it onto continuation lines if it is too long.
+fn push_line(ics: &mut String, line: &str) {
+	let mut length = 0;
+
+	for c in line.chars() {
+		// Never split a multi-byte character across lines.
+		if length + c.len_utf8() > MAX_LINE_LENGTH {
+			ics.push_str("\r\n ");
+			// The leading space counts towards the length of the continuation line.
+			length = 1;
+		}
+
+		ics.push(c);
+		length += c.len_utf8();
+	}
+
+	ics.push_str("\r\n");
+}
@@ -0,0 +1,80 @@
+/*!
+A local copy of the catalog, which can be saved to disk and searched or exported without the network.
+
+```no_run
+use roosterteeth_rs::catalog::Catalog;
+use roosterteeth_rs::requests::{Credential, Requests};
+
+let requests = Requests::new(Credential::Anonymous);
+
+// Syncing everything takes a request per show and per season, so limit it to a channel where possible.
+let catalog = requests.sync_catalog(Some(&"achievement-hunter".parse().unwrap()));
+catalog.save("achievement-hunter.json").unwrap();
+
+let catalog = Catalog::load("achievement-hunter.json").unwrap();
+println!("{} episodes", catalog.episodes.len());
+```
+*/
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::structs::ids::{SeasonSlug, ShowSlug};
+use crate::structs::*;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Catalog {
+	/// When the catalog was downloaded.
+	pub synced_at: DateTime<Utc>,
+	pub channels: Vec<channels::Channel>,
+	pub series: Vec<series::Series>,
+	pub seasons: Vec<seasons::Season>,
+	pub episodes: Vec<episodes::Episode>,
+}
+
+impl Catalog {
+	/// Reads a catalog previously written with [save](#method.save).
+	pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Catalog> {
+		let reader = BufReader::new(File::open(path)?);
+
+		serde_json::from_reader(reader).map_err(io::Error::from)
+	}
+
+	/// Writes the catalog as json.
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let mut writer = BufWriter::new(File::create(path)?);
+
+		serde_json::to_writer(&mut writer, self)?;
+		// Dropping the writer would ignore a failure to write what's left in the buffer.
+		writer.flush()
+	}
+
+	pub fn find_series(&self, slug: &ShowSlug) -> Option<&series::Series> {
+		self.series
+			.iter()
+			.find(|series| &series.attributes.slug == slug)
+	}
+
+	/// The seasons of a show, in the order they were synced.
+	pub fn seasons_of<'a>(
+		&'a self,
+		show: &'a ShowSlug,
+	) -> impl Iterator<Item = &'a seasons::Season> {
+		self.seasons
+			.iter()
+			.filter(move |season| &season.attributes.show_slug == show)
+	}
+
+	/// The episodes of a season, in the order they were synced.
+	pub fn episodes_of<'a>(
+		&'a self,
+		season: &'a SeasonSlug,
+	) -> impl Iterator<Item = &'a episodes::Episode> {
+		self.episodes
+			.iter()
+			.filter(move |episode| &episode.attributes.season_slug == season)
+	}
+}
@@ -0,0 +1,344 @@
+/*!
+Compares two snapshots of the catalog, such as two syncs of a mirror taken a week apart.
+
+Shows, seasons and episodes are matched by slug. The diff lists what was added and removed, along with
+changed titles and descriptions, go-live dates, sponsor only flags and blacklisted countries.
+
+```no_run
+use roosterteeth_rs::catalog::Catalog;
+
+let old = Catalog::load("last-week.json").unwrap();
+let new = Catalog::load("today.json").unwrap();
+
+let diff = old.diff(&new);
+if !diff.is_empty() {
+	// Either a human readable summary, or json for other tools.
+	println!("{}", diff);
+	println!("{}", serde_json::to_string_pretty(&diff).unwrap());
+}
+```
+*/
+
+use chrono::offset::FixedOffset;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::catalog::Catalog;
+use crate::geo::{Country, Restricted};
+use crate::schedule::Tier;
+use crate::structs::episodes::Episode;
+use crate::structs::ids::{EpisodeSlug, SeasonSlug, ShowSlug};
+use crate::structs::seasons::Season;
+use crate::structs::series::Series;
+
+/// The differences between an older and a newer snapshot of the catalog.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diff {
+	/// When the older snapshot was synced.
+	pub from: DateTime<Utc>,
+	/// When the newer snapshot was synced.
+	pub to: DateTime<Utc>,
+	pub shows: Section<ShowSlug>,
+	pub seasons: Section<SeasonSlug>,
+	pub episodes: Section<EpisodeSlug>,
+}
+
+/// What was added, removed and changed for one kind of item, sorted by slug.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Section<S> {
+	pub added: Vec<S>,
+	pub removed: Vec<S>,
+	pub changed: Vec<Changed<S>>,
+}
+
+impl<S> Section<S> {
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+	}
+}
+
+/// An item that is in both snapshots, but has changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Changed<S> {
+	pub slug: S,
+	pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "field", rename_all = "snake_case")]
+pub enum Change {
+	Title {
+		from: String,
+		to: String,
+	},
+	/// The description of a season or episode, or the summary of a show.
+	Description {
+		from: String,
+		to: String,
+	},
+	/// An episode goes live for a tier at a different time.
+	GoLive {
+		tier: Tier,
+		from: DateTime<FixedOffset>,
+		to: DateTime<FixedOffset>,
+	},
+	SponsorsOnly {
+		from: bool,
+		to: bool,
+	},
+	BlacklistedCountries {
+		added: Vec<Country>,
+		removed: Vec<Country>,
+	},
+}
+
+impl Diff {
+	/// Compares two catalogs, where `old` is the earlier snapshot.
+	pub fn new(old: &Catalog, new: &Catalog) -> Diff {
+		Diff {
+			from: old.synced_at,
+			to: new.synced_at,
+			shows: section(
+				&old.series,
+				&new.series,
+				|series| &series.attributes.slug,
+				series_changes,
+			),
+			seasons: section(
+				&old.seasons,
+				&new.seasons,
+				|season| &season.attributes.slug,
+				season_changes,
+			),
+			episodes: section(
+				&old.episodes,
+				&new.episodes,
+				|episode| &episode.attributes.slug,
+				episode_changes,
+			),
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.shows.is_empty() && self.seasons.is_empty() && self.episodes.is_empty()
+	}
+}
+
+impl Catalog {
+	/// Compares this catalog to a newer snapshot.
+	pub fn diff(&self, newer: &Catalog) -> Diff {
+		Diff::new(self, newer)
+	}
+}
+
+fn section<'a, T, S, K, C>(old: &'a [T], new: &'a [T], slug: K, changes: C) -> Section<S>
+where
+	S: Ord + Clone + 'a,
+	K: Fn(&'a T) -> &'a S,
+	C: Fn(&T, &T) -> Vec<Change>,
+{
+	let old: BTreeMap<&S, &T> = old.iter().map(|item| (slug(item), item)).collect();
+	let new: BTreeMap<&S, &T> = new.iter().map(|item| (slug(item), item)).collect();
+
+	let mut section = Section {
+		added: Vec::new(),
+		removed: Vec::new(),
+		changed: Vec::new(),
+	};
+
+	for (slug, item) in &new {
+		match old.get(slug) {
+			Some(previous) => {
+				let changes = changes(previous, item);
+				if !changes.is_empty() {
+					section.changed.push(Changed {
+						slug: (*slug).clone(),
+						changes,
+					});
+				}
+			}
+			None => section.added.push((*slug).clone()),
+		}
+	}
+	section.removed = old
+		.keys()
+		.filter(|slug| !new.contains_key(*slug))
+		.map(|slug| (*slug).clone())
+		.collect();
+
+	section
+}
+
+fn series_changes(old: &Series, new: &Series) -> Vec<Change> {
+	let (from, to) = (&old.attributes, &new.attributes);
+	let mut changes = Vec::new();
+
+	push_text(&mut changes, &from.title, &to.title, title);
+	push_text(&mut changes, &from.summary, &to.summary, description);
+	push_sponsors_only(&mut changes, from.is_sponsors_only, to.is_sponsors_only);
+	push_countries(&mut changes, old, new);
+
+	changes
+}
+
+fn season_changes(old: &Season, new: &Season) -> Vec<Change> {
+	let (from, to) = (&old.attributes, &new.attributes);
+	let mut changes = Vec::new();
+
+	push_text(&mut changes, &from.title, &to.title, title);
+	push_text(
+		&mut changes,
+		&from.description,
+		&to.description,
+		description,
+	);
+
+	changes
+}
+
+fn episode_changes(old: &Episode, new: &Episode) -> Vec<Change> {
+	let (from, to) = (&old.attributes, &new.attributes);
+	let mut changes = Vec::new();
+
+	push_text(&mut changes, &from.title, &to.title, title);
+	push_text(
+		&mut changes,
+		&from.description,
+		&to.description,
+		description,
+	);
+
+	for tier in Tier::ALL.iter().copied() {
+		let (from, to) = (old.golive_for(tier), new.golive_for(tier));
+		if from != to {
+			changes.push(Change::GoLive { tier, from, to });
+		}
+	}
+
+	push_sponsors_only(&mut changes, from.is_sponsors_only, to.is_sponsors_only);
+	push_countries(&mut changes, old, new);
+
+	changes
+}
+
+fn title(from: String, to: String) -> Change {
+	Change::Title { from, to }
+}
+
+fn description(from: String, to: String) -> Change {
+	Change::Description { from, to }
+}
+
+fn push_text(
+	changes: &mut Vec<Change>,
+	from: &str,
+	to: &str,
+	change: fn(String, String) -> Change,
+) {
+	if from != to {
+		changes.push(change(from.to_string(), to.to_string()));
+	}
+}
+
+fn push_sponsors_only(changes: &mut Vec<Change>, from: bool, to: bool) {
+	if from != to {
+		changes.push(Change::SponsorsOnly { from, to });
+	}
+}
+
+fn push_countries<T: Restricted>(changes: &mut Vec<Change>, old: &T, new: &T) {
+	let (from, to) = (old.blocked_countries(), new.blocked_countries());
+
+	let mut added: Vec<Country> = to.iter().filter(|c| !from.contains(c)).copied().collect();
+	let mut removed: Vec<Country> = from.iter().filter(|c| !to.contains(c)).copied().collect();
+
+	if !added.is_empty() || !removed.is_empty() {
+		added.sort();
+		removed.sort();
+		changes.push(Change::BlacklistedCountries { added, removed });
+	}
+}
+
+impl fmt::Display for Diff {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(
+			f,
+			"Changes from {} to {}",
+			self.from.to_rfc3339(),
+			self.to.to_rfc3339()
+		)?;
+
+		if self.is_empty() {
+			return writeln!(f, "No changes");
+		}
+
+		write_section(f, "Shows", &self.shows)?;
+		write_section(f, "Seasons", &self.seasons)?;
+		write_section(f, "Episodes", &self.episodes)
+	}
+}
+
+fn write_section<S: fmt::Display>(
+	f: &mut fmt::Formatter,
+	name: &str,
+	section: &Section<S>,
+) -> fmt::Result {
+	if section.is_empty() {
+		return Ok(());
+	}
+
+	writeln!(f)?;
+	writeln!(
+		f,
+		"{}: {} added, {} removed, {} changed",
+		name,
+		section.added.len(),
+		section.removed.len(),
+		section.changed.len()
+	)?;
+
+	for slug in &section.added {
+		writeln!(f, "  + {}", slug)?;
+	}
+	for slug in &section.removed {
+		writeln!(f, "  - {}", slug)?;
+	}
+	for changed in &section.changed {
+		writeln!(f, "  ~ {}", changed.slug)?;
+		for change in &changed.changes {
+			writeln!(f, "      {}", change)?;
+		}
+	}
+
+	Ok(())
+}
+
+impl fmt::Display for Change {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Change::Title { from, to } => write!(f, "title: {:?} -> {:?}", from, to),
+			// Descriptions are often long, so only note that they changed.
+			Change::Description { .. } => write!(f, "description changed"),
+			Change::GoLive { tier, from, to } => write!(
+				f,
+				"{} go-live: {} -> {}",
+				tier,
+				from.to_rfc3339(),
+				to.to_rfc3339()
+			),
+			Change::SponsorsOnly { from, to } => write!(f, "sponsors only: {} -> {}", from, to),
+			Change::BlacklistedCountries { added, removed } => {
+				write!(f, "blacklisted countries:")?;
+				for country in added {
+					write!(f, " +{}", country)?;
+				}
+				for country in removed {
+					write!(f, " -{}", country)?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
@@ -0,0 +1,688 @@
+/*!
+Flattens channels, series, seasons and episodes into rows for bulk exports to JSON Lines, CSV and Parquet.
+
+Lists such as an episode's tags, genres and cast can either be [joined](./enum.Nested.html#variant.Joined)
+into a single column, or [exploded](./enum.Nested.html#variant.Exploded) into a row per value.
+
+```no_run
+use roosterteeth_rs::export::{self, Csv, Nested};
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::structs::episodes::Episode;
+use std::fs::File;
+
+let requests = Requests::new(Credential::Anonymous);
+let mut writer = Csv::new(File::create("episodes.csv").unwrap());
+
+// Episodes are written as each page is downloaded, so memory use doesn't grow with the catalog.
+let rows = export::write::<Episode, _, _>(requests.iter_episodes(None, Some("asc")), Nested::Joined, &mut writer).unwrap();
+println!("Exported {} rows", rows);
+```
+
+Parquet files can be written with the `parquet` feature enabled.
+*/
+
+use chrono::offset::FixedOffset;
+use chrono::DateTime;
+use serde_json::{Map, Value as Json};
+use std::borrow::Borrow;
+use std::io::{self, Write};
+
+use crate::structs::channels::Channel;
+use crate::structs::episodes::Episode;
+use crate::structs::seasons::Season;
+use crate::structs::series::Series;
+
+/// Separates the values of a joined list.
+pub const SEPARATOR: &str = "|";
+
+/// How lists nested in a row are exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nested {
+	/// Each list is a column, with its values separated by [SEPARATOR](./constant.SEPARATOR.html).
+	Joined,
+	/// Each value is its own row, with a `nested` column naming the list and a `value` column.
+	/// The rest of the row is repeated, and items without any nested values are a single row with both empty.
+	Exploded,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+	Text,
+	Integer,
+	Boolean,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+	pub name: &'static str,
+	pub kind: Type,
+}
+
+const fn text(name: &'static str) -> Column {
+	Column {
+		name,
+		kind: Type::Text,
+	}
+}
+
+const fn integer(name: &'static str) -> Column {
+	Column {
+		name,
+		kind: Type::Integer,
+	}
+}
+
+const fn boolean(name: &'static str) -> Column {
+	Column {
+		name,
+		kind: Type::Boolean,
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+	Text(String),
+	Integer(i64),
+	Boolean(bool),
+	Null,
+}
+
+impl Value {
+	fn to_json(&self) -> Json {
+		match self {
+			Value::Text(text) => Json::from(text.as_str()),
+			Value::Integer(integer) => Json::from(*integer),
+			Value::Boolean(boolean) => Json::from(*boolean),
+			Value::Null => Json::Null,
+		}
+	}
+
+	fn to_text(&self) -> String {
+		match self {
+			Value::Text(text) => text.clone(),
+			Value::Integer(integer) => integer.to_string(),
+			Value::Boolean(boolean) => boolean.to_string(),
+			Value::Null => String::new(),
+		}
+	}
+}
+
+impl From<&str> for Value {
+	fn from(text: &str) -> Self {
+		Value::Text(text.to_string())
+	}
+}
+
+impl From<String> for Value {
+	fn from(text: String) -> Self {
+		Value::Text(text)
+	}
+}
+
+impl From<&DateTime<FixedOffset>> for Value {
+	fn from(date: &DateTime<FixedOffset>) -> Self {
+		Value::Text(date.to_rfc3339())
+	}
+}
+
+impl From<bool> for Value {
+	fn from(boolean: bool) -> Self {
+		Value::Boolean(boolean)
+	}
+}
+
+macro_rules! integer_value {
+	($($kind:ty),*) => {
+		$(
+			impl From<$kind> for Value {
+				fn from(integer: $kind) -> Self {
+					Value::Integer(integer.into())
+				}
+			}
+		)*
+	};
+}
+
+integer_value!(i8, u16, u32);
+
+/// A model that can be exported as rows.
+pub trait Flatten {
+	/// The columns of the model itself, in the order of [values](#tymethod.values).
+	const COLUMNS: &'static [Column];
+	/// The names of the nested lists, in the order of [nested](#method.nested).
+	const NESTED: &'static [&'static str] = &[];
+
+	fn values(&self) -> Vec<Value>;
+
+	fn nested(&self) -> Vec<Vec<String>> {
+		Vec::new()
+	}
+}
+
+/// The columns of the rows exported for a model.
+pub fn columns<T: Flatten>(nested: Nested) -> Vec<Column> {
+	let mut columns = T::COLUMNS.to_vec();
+
+	match nested {
+		Nested::Joined => columns.extend(T::NESTED.iter().map(|name| text(name))),
+		Nested::Exploded if !T::NESTED.is_empty() => {
+			columns.push(text("nested"));
+			columns.push(text("value"));
+		}
+		Nested::Exploded => {}
+	}
+
+	columns
+}
+
+/// Flattens an item into one row, or a row per nested value when exploded.
+pub fn rows<T: Flatten>(item: &T, nested: Nested) -> Vec<Vec<Value>> {
+	let values = item.values();
+
+	if T::NESTED.is_empty() {
+		return vec![values];
+	}
+
+	match nested {
+		Nested::Joined => {
+			let mut row = values;
+			row.extend(
+				item.nested()
+					.into_iter()
+					.map(|list| Value::Text(list.join(SEPARATOR))),
+			);
+
+			vec![row]
+		}
+		Nested::Exploded => {
+			let mut rows = Vec::new();
+
+			for (name, list) in T::NESTED.iter().zip(item.nested()) {
+				for value in list {
+					let mut row = values.clone();
+					row.push(Value::from(*name));
+					row.push(Value::Text(value));
+					rows.push(row);
+				}
+			}
+
+			if rows.is_empty() {
+				let mut row = values;
+				row.push(Value::Null);
+				row.push(Value::Null);
+				rows.push(row);
+			}
+
+			rows
+		}
+	}
+}
+
+/// A destination for exported rows.
+pub trait Writer {
+	/// Called once with the columns before any rows are written.
+	fn begin(&mut self, columns: &[Column]) -> io::Result<()>;
+	fn write_row(&mut self, row: &[Value]) -> io::Result<()>;
+	/// Flushes any buffered rows, and writes footers for formats that have them.
+	fn finish(&mut self) -> io::Result<()>;
+}
+
+/**
+Flattens each item and writes it, returning the number of rows written.
+
+Items are consumed one at a time, so paginated iterators such as
+[iter_episodes](../requests/struct.Requests.html#method.iter_episodes) keep only a single page in memory.
+*/
+pub fn write<T, I, W>(items: I, nested: Nested, writer: &mut W) -> io::Result<usize>
+where
+	T: Flatten,
+	I: IntoIterator,
+	I::Item: Borrow<T>,
+	W: Writer + ?Sized,
+{
+	writer.begin(&columns::<T>(nested))?;
+
+	let mut count = 0;
+	for item in items {
+		for row in rows(item.borrow(), nested) {
+			writer.write_row(&row)?;
+			count += 1;
+		}
+	}
+
+	writer.finish()?;
+
+	Ok(count)
+}
+
+/// Writes each row as a json object on its own line.
+pub struct JsonLines<W: Write> {
+	writer: W,
+	columns: Vec<&'static str>,
+}
+
+impl<W: Write> JsonLines<W> {
+	pub fn new(writer: W) -> Self {
+		JsonLines {
+			writer,
+			columns: Vec::new(),
+		}
+	}
+
+	pub fn into_inner(self) -> W {
+		self.writer
+	}
+}
+
+impl<W: Write> Writer for JsonLines<W> {
+	fn begin(&mut self, columns: &[Column]) -> io::Result<()> {
+		self.columns = columns.iter().map(|column| column.name).collect();
+
+		Ok(())
+	}
+
+	fn write_row(&mut self, row: &[Value]) -> io::Result<()> {
+		let object: Map<String, Json> = self
+			.columns
+			.iter()
+			.zip(row)
+			.map(|(name, value)| (name.to_string(), value.to_json()))
+			.collect();
+
+		serde_json::to_writer(&mut self.writer, &object)?;
+		self.writer.write_all(b"\n")
+	}
+
+	fn finish(&mut self) -> io::Result<()> {
+		self.writer.flush()
+	}
+}
+
+/// Writes rows as RFC 4180 CSV, with a header row.
+pub struct Csv<W: Write> {
+	writer: W,
+}
+
+impl<W: Write> Csv<W> {
+	pub fn new(writer: W) -> Self {
+		Csv { writer }
+	}
+
+	pub fn into_inner(self) -> W {
+		self.writer
+	}
+
+	fn write_record<I: IntoIterator<Item = String>>(&mut self, fields: I) -> io::Result<()> {
+		let line: Vec<String> = fields.into_iter().map(|field| quote(&field)).collect();
+
+		self.writer.write_all(line.join(",").as_bytes())?;
+		self.writer.write_all(b"\r\n")
+	}
+}
+
+impl<W: Write> Writer for Csv<W> {
+	fn begin(&mut self, columns: &[Column]) -> io::Result<()> {
+		self.write_record(columns.iter().map(|column| column.name.to_string()))
+	}
+
+	fn write_row(&mut self, row: &[Value]) -> io::Result<()> {
+		self.write_record(row.iter().map(Value::to_text))
+	}
+
+	fn finish(&mut self) -> io::Result<()> {
+		self.writer.flush()
+	}
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote or line break.
+fn quote(field: &str) -> String {
+	if field.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", field.replace('"', "\"\""))
+	} else {
+		field.to_string()
+	}
+}
+
+#[cfg(feature = "parquet")]
+pub use self::parquet::Parquet;
+
+#[cfg(feature = "parquet")]
+mod parquet {
+	use parquet::basic::{ConvertedType, Repetition, Type as PhysicalType};
+	use parquet::data_type::{BoolType, ByteArray, ByteArrayType, Int64Type};
+	use parquet::errors::ParquetError;
+	use parquet::file::properties::WriterProperties;
+	use parquet::file::writer::SerializedFileWriter;
+	use parquet::schema::types::Type as Schema;
+	use std::io::{self, Write};
+	use std::sync::Arc;
+
+	use super::{Column, Type, Value, Writer};
+
+	/// The number of rows buffered before they are written as a row group.
+	const ROW_GROUP_SIZE: usize = 10_000;
+
+	/// Writes rows as a Parquet file, buffering up to 10,000 rows at a time into row groups.
+	pub struct Parquet<W: Write + Send> {
+		output: Option<W>,
+		writer: Option<SerializedFileWriter<W>>,
+		columns: Vec<Column>,
+		rows: Vec<Vec<Value>>,
+	}
+
+	impl<W: Write + Send> Parquet<W> {
+		pub fn new(output: W) -> Self {
+			Parquet {
+				output: Some(output),
+				writer: None,
+				columns: Vec::new(),
+				rows: Vec::new(),
+			}
+		}
+
+		fn flush_rows(&mut self) -> Result<(), ParquetError> {
+			let writer = match &mut self.writer {
+				Some(writer) if !self.rows.is_empty() => writer,
+				_ => return Ok(()),
+			};
+
+			let mut row_group = writer.next_row_group()?;
+			for (index, column) in self.columns.iter().enumerate() {
+				let values = self.rows.iter().map(|row| &row[index]);
+				let levels: Vec<i16> = values
+					.clone()
+					.map(|value| if *value == Value::Null { 0 } else { 1 })
+					.collect();
+
+				let mut column_writer = row_group.next_column()?.unwrap();
+				match column.kind {
+					Type::Text => {
+						let data: Vec<ByteArray> = values
+							.filter_map(|value| match value {
+								Value::Null => None,
+								value => Some(ByteArray::from(value.to_text().as_str())),
+							})
+							.collect();
+						column_writer.typed::<ByteArrayType>().write_batch(
+							&data,
+							Some(&levels),
+							None,
+						)?;
+					}
+					Type::Integer => {
+						let data: Vec<i64> = values
+							.filter_map(|value| match value {
+								Value::Integer(integer) => Some(*integer),
+								_ => None,
+							})
+							.collect();
+						column_writer.typed::<Int64Type>().write_batch(
+							&data,
+							Some(&levels),
+							None,
+						)?;
+					}
+					Type::Boolean => {
+						let data: Vec<bool> = values
+							.filter_map(|value| match value {
+								Value::Boolean(boolean) => Some(*boolean),
+								_ => None,
+							})
+							.collect();
+						column_writer.typed::<BoolType>().write_batch(
+							&data,
+							Some(&levels),
+							None,
+						)?;
+					}
+				}
+				column_writer.close()?;
+			}
+			row_group.close()?;
+
+			self.rows.clear();
+
+			Ok(())
+		}
+	}
+
+	impl<W: Write + Send> Writer for Parquet<W> {
+		fn begin(&mut self, columns: &[Column]) -> io::Result<()> {
+			let fields = columns
+				.iter()
+				.map(|column| {
+					let builder = match column.kind {
+						Type::Text => {
+							Schema::primitive_type_builder(column.name, PhysicalType::BYTE_ARRAY)
+								.with_converted_type(ConvertedType::UTF8)
+						}
+						Type::Integer => {
+							Schema::primitive_type_builder(column.name, PhysicalType::INT64)
+						}
+						Type::Boolean => {
+							Schema::primitive_type_builder(column.name, PhysicalType::BOOLEAN)
+						}
+					};
+
+					builder
+						.with_repetition(Repetition::OPTIONAL)
+						.build()
+						.map(Arc::new)
+				})
+				.collect::<Result<Vec<_>, _>>()
+				.map_err(to_io)?;
+
+			let schema = Schema::group_type_builder("schema")
+				.with_fields(fields)
+				.build()
+				.map_err(to_io)?;
+
+			let output = self.output.take().expect("begin called twice");
+			self.writer = Some(
+				SerializedFileWriter::new(
+					output,
+					Arc::new(schema),
+					Arc::new(WriterProperties::builder().build()),
+				)
+				.map_err(to_io)?,
+			);
+			self.columns = columns.to_vec();
+
+			Ok(())
+		}
+
+		fn write_row(&mut self, row: &[Value]) -> io::Result<()> {
+			self.rows.push(row.to_vec());
+
+			if self.rows.len() >= ROW_GROUP_SIZE {
+				self.flush_rows().map_err(to_io)?;
+			}
+
+			Ok(())
+		}
+
+		fn finish(&mut self) -> io::Result<()> {
+			self.flush_rows().map_err(to_io)?;
+
+			if let Some(writer) = self.writer.take() {
+				writer.close().map_err(to_io)?;
+			}
+
+			Ok(())
+		}
+	}
+
+	fn to_io(error: ParquetError) -> io::Error {
+		io::Error::other(error)
+	}
+}
+
+impl Flatten for Channel {
+	const COLUMNS: &'static [Column] = &[
+		integer("id"),
+		text("uuid"),
+		text("slug"),
+		text("name"),
+		integer("importance"),
+		text("brand_color"),
+	];
+
+	fn values(&self) -> Vec<Value> {
+		let attributes = &self.attributes;
+
+		vec![
+			self.id.0.into(),
+			self.uuid.to_string().into(),
+			attributes.slug.as_str().into(),
+			attributes.name.as_str().into(),
+			attributes.importance.into(),
+			attributes.brand_color.as_str().into(),
+		]
+	}
+}
+
+impl Flatten for Series {
+	const COLUMNS: &'static [Column] = &[
+		integer("id"),
+		text("uuid"),
+		text("slug"),
+		text("title"),
+		text("channel_slug"),
+		text("category"),
+		integer("season_count"),
+		integer("episode_count"),
+		boolean("is_sponsors_only"),
+		text("published_at"),
+		text("updated_at"),
+		text("last_episode_golive_at"),
+		text("summary"),
+	];
+	const NESTED: &'static [&'static str] = &["genres", "blacklisted_countries"];
+
+	fn values(&self) -> Vec<Value> {
+		let attributes = &self.attributes;
+
+		vec![
+			self.id.0.into(),
+			self.uuid.to_string().into(),
+			attributes.slug.as_str().into(),
+			attributes.title.as_str().into(),
+			attributes.channel_slug.as_str().into(),
+			attributes.category.as_str().into(),
+			attributes.season_count.into(),
+			attributes.episode_count.into(),
+			attributes.is_sponsors_only.into(),
+			(&attributes.published_at).into(),
+			(&attributes.updated_at).into(),
+			(&attributes.last_episode_golive_at).into(),
+			attributes.summary.as_str().into(),
+		]
+	}
+
+	fn nested(&self) -> Vec<Vec<String>> {
+		vec![
+			self.attributes.genres.clone(),
+			self.attributes.blacklisted_countries.clone(),
+		]
+	}
+}
+
+impl Flatten for Season {
+	const COLUMNS: &'static [Column] = &[
+		integer("id"),
+		text("uuid"),
+		text("slug"),
+		text("title"),
+		integer("number"),
+		text("show_slug"),
+		text("published_at"),
+		text("description"),
+	];
+
+	fn values(&self) -> Vec<Value> {
+		let attributes = &self.attributes;
+
+		vec![
+			self.id.0.into(),
+			self.uuid.to_string().into(),
+			attributes.slug.as_str().into(),
+			attributes.title.as_str().into(),
+			attributes.number.into(),
+			attributes.show_slug.as_str().into(),
+			(&attributes.published_at).into(),
+			attributes.description.as_str().into(),
+		]
+	}
+}
+
+impl Flatten for Episode {
+	const COLUMNS: &'static [Column] = &[
+		integer("id"),
+		text("uuid"),
+		text("slug"),
+		text("title"),
+		text("show_slug"),
+		text("show_title"),
+		text("season_slug"),
+		integer("season_number"),
+		integer("number"),
+		integer("sort_number"),
+		text("channel_slug"),
+		integer("length"),
+		boolean("is_sponsors_only"),
+		integer("member_tier_i"),
+		text("public_golive_at"),
+		text("member_golive_at"),
+		text("sponsor_golive_at"),
+		text("original_air_date"),
+		text("caption"),
+		text("description"),
+	];
+	const NESTED: &'static [&'static str] = &["tags", "genres", "cast", "blacklisted_countries"];
+
+	fn values(&self) -> Vec<Value> {
+		let attributes = &self.attributes;
+
+		vec![
+			self.id.0.into(),
+			self.uuid.to_string().into(),
+			attributes.slug.as_str().into(),
+			attributes.title.as_str().into(),
+			attributes.show_slug.as_str().into(),
+			attributes.show_title.as_str().into(),
+			attributes.season_slug.as_str().into(),
+			attributes.season_number.into(),
+			attributes.number.into(),
+			attributes.sort_number.into(),
+			attributes.channel_slug.as_str().into(),
+			attributes.length.into(),
+			attributes.is_sponsors_only.into(),
+			attributes.member_tier_i.into(),
+			(&attributes.public_golive_at).into(),
+			(&attributes.member_golive_at).into(),
+			(&attributes.sponsor_golive_at).into(),
+			(&attributes.original_air_date).into(),
+			attributes.caption.as_str().into(),
+			attributes.description.as_str().into(),
+		]
+	}
+
+	fn nested(&self) -> Vec<Vec<String>> {
+		let included = &self.included;
+
+		vec![
+			included
+				.tags
+				.iter()
+				.map(|tag| tag.attributes.tag.clone())
+				.collect(),
+			self.attributes.genres.clone(),
+			included
+				.cast_members
+				.iter()
+				.map(|member| member.attributes.name.clone())
+				.collect(),
+			self.attributes.blacklisted_countries.clone(),
+		]
+	}
+}
@@ -0,0 +1,362 @@
+/*!
+Generates [RSS 2.0](https://www.rssboard.org/rss-specification) and [Atom](https://tools.ietf.org/html/rfc4287)
+feeds from a list of episodes, such as those returned by `get_season_episodes()` or `list_episodes()` with a channel.
+
+```no_run
+use roosterteeth_rs::feed::Feed;
+use roosterteeth_rs::requests::{Credential, Requests};
+
+let requests = Requests::new(Credential::Anonymous);
+let series = requests.get_series(&"red-vs-blue".parse().unwrap());
+let episodes = requests.get_season_episodes(&"red-vs-blue-season-1".parse().unwrap(), None);
+
+let feed = Feed::for_series(&series, &episodes);
+std::fs::write("red-vs-blue.xml", feed.to_rss()).unwrap();
+```
+
+Audio first shows can be published as a podcast by setting [podcast](./struct.Feed.html#structfield.podcast),
+which adds the iTunes specific tags podcast apps expect. Podcast apps download the enclosure of each item,
+so the episode's media has to be added to [media](./struct.Feed.html#structfield.media):
+
+```no_run
+use roosterteeth_rs::feed::{Enclosure, Feed, Podcast};
+use roosterteeth_rs::requests::{Credential, Requests};
+
+let requests = Requests::new(Credential::Anonymous);
+let series = requests.get_series(&"rt-podcast".parse().unwrap());
+let episodes = requests.get_season_episodes(&"rt-podcast-2020".parse().unwrap(), None);
+
+let mut feed = Feed::for_series(&series, &episodes);
+feed.podcast = Some(Podcast {
+	author: String::from("Rooster Teeth"),
+	category: String::from("Comedy"),
+	explicit: true,
+	image: None,
+});
+for episode in &episodes {
+	if let Ok(video) = requests.get_video(&episode.attributes.slug) {
+		feed.media.insert(episode.uuid, Enclosure::from_video(&video));
+	}
+}
+```
+*/
+
+use chrono::offset::FixedOffset;
+use chrono::DateTime;
+use std::collections::HashMap;
+
+use crate::structs::channels::Channel;
+use crate::structs::common::{best_image, Image};
+use crate::structs::episodes::Episode;
+use crate::structs::ids::EpisodeUuid;
+use crate::structs::series::Series;
+use crate::structs::videos::Video;
+use crate::xml::{escape, push_element};
+
+const WEBSITE_URL: &str = "https://roosterteeth.com";
+const GENERATOR: &str = "roosterteeth-rs";
+
+/// The iTunes podcast metadata of a feed.
+#[derive(Debug, Clone)]
+pub struct Podcast {
+	pub author: String,
+	/// One of the [Apple Podcasts categories](https://help.apple.com/itc/podcasts_connect/#/itc9267a2f12), e.g. "Comedy".
+	pub category: String,
+	pub explicit: bool,
+	/// The square cover art of the podcast.
+	pub image: Option<String>,
+}
+
+/// The audio or video file of an item, which podcast apps download.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enclosure {
+	pub url: String,
+	/// The size of the file in bytes, 0 when it isn't known.
+	pub length: u64,
+	pub mime_type: String,
+}
+
+impl Enclosure {
+	/// The downloadable file of a video, whose size isn't part of the api.
+	pub fn from_video(video: &Video) -> Self {
+		let url = &video.links.download;
+
+		Enclosure {
+			url: url.clone(),
+			length: 0,
+			mime_type: media_type(url).to_string(),
+		}
+	}
+}
+
+/// A feed of episodes, rendered with either [to_rss](#method.to_rss) or [to_atom](#method.to_atom).
+#[derive(Debug, Clone)]
+pub struct Feed<'a> {
+	pub title: String,
+	/// The public web page the feed describes.
+	pub link: String,
+	pub description: String,
+	pub episodes: &'a [Episode],
+	pub podcast: Option<Podcast>,
+	/// The media of each episode, used as its enclosure.
+	/// Without it, RSS items fall back to the thumbnail, except in podcasts, which only take audio and video.
+	pub media: HashMap<EpisodeUuid, Enclosure>,
+}
+
+impl<'a> Feed<'a> {
+	pub fn new(title: &str, link: &str, description: &str, episodes: &'a [Episode]) -> Self {
+		Feed {
+			title: title.to_string(),
+			link: link.to_string(),
+			description: description.to_string(),
+			episodes,
+			podcast: None,
+			media: HashMap::new(),
+		}
+	}
+
+	/// Creates a feed for a show, linking to its series page.
+	pub fn for_series(series: &Series, episodes: &'a [Episode]) -> Self {
+		Feed::new(
+			&series.attributes.title,
+			&format!("{}{}", WEBSITE_URL, series.canonical_links.reference),
+			&series.attributes.summary,
+			episodes,
+		)
+	}
+
+	/// Creates a feed for a channel, linking to its channel page.
+	pub fn for_channel(channel: &Channel, episodes: &'a [Episode]) -> Self {
+		Feed::new(
+			&channel.attributes.name,
+			&format!("{}/channel/{}", WEBSITE_URL, channel.attributes.slug),
+			&format!("The latest episodes from {}", channel.attributes.name),
+			episodes,
+		)
+	}
+
+	/// Renders the feed as an RSS 2.0 document.
+	pub fn to_rss(&self) -> String {
+		let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+		if self.podcast.is_some() {
+			xml.push_str("<rss version=\"2.0\" xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\">\n");
+		} else {
+			xml.push_str("<rss version=\"2.0\">\n");
+		}
+
+		xml.push_str("<channel>\n");
+		push_element(&mut xml, 1, "title", &self.title);
+		push_element(&mut xml, 1, "link", &self.link);
+		push_element(&mut xml, 1, "description", &self.description);
+		push_element(&mut xml, 1, "generator", GENERATOR);
+		if let Some(updated) = self.updated() {
+			push_element(&mut xml, 1, "lastBuildDate", &updated.to_rfc2822());
+		}
+
+		if let Some(podcast) = &self.podcast {
+			push_element(&mut xml, 1, "itunes:author", &podcast.author);
+			push_element(&mut xml, 1, "itunes:summary", &self.description);
+			push_element(
+				&mut xml,
+				1,
+				"itunes:explicit",
+				if podcast.explicit { "true" } else { "false" },
+			);
+			xml.push_str(&format!(
+				"\t<itunes:category text=\"{}\"/>\n",
+				escape(&podcast.category)
+			));
+			if let Some(image) = &podcast.image {
+				xml.push_str(&format!("\t<itunes:image href=\"{}\"/>\n", escape(image)));
+			}
+		}
+
+		for episode in self.episodes {
+			self.push_rss_item(&mut xml, episode);
+		}
+
+		xml.push_str("</channel>\n</rss>\n");
+
+		xml
+	}
+
+	fn push_rss_item(&self, xml: &mut String, episode: &Episode) {
+		let attributes = &episode.attributes;
+
+		xml.push_str("\t<item>\n");
+		push_element(xml, 2, "title", &attributes.title);
+		push_element(xml, 2, "link", &episode_link(episode));
+		xml.push_str(&format!(
+			"\t\t<guid isPermaLink=\"false\">{}</guid>\n",
+			episode.uuid
+		));
+		push_element(xml, 2, "pubDate", &attributes.public_golive_at.to_rfc2822());
+		push_element(xml, 2, "description", &attributes.description);
+		for genre in &attributes.genres {
+			push_element(xml, 2, "category", genre);
+		}
+
+		match (self.media.get(&episode.uuid), thumbnail(episode)) {
+			(Some(media), _) => xml.push_str(&format!(
+				"\t\t<enclosure url=\"{}\" length=\"{}\" type=\"{}\"/>\n",
+				escape(&media.url),
+				media.length,
+				escape(&media.mime_type),
+			)),
+			// The artwork of a podcast episode goes in itunes:image instead.
+			(None, Some(image)) if self.podcast.is_none() => xml.push_str(&format!(
+				"\t\t<enclosure url=\"{}\" length=\"0\" type=\"{}\"/>\n",
+				escape(&image.attributes.large),
+				mime_type(&image.attributes.large),
+			)),
+			_ => {}
+		}
+
+		if self.podcast.is_some() {
+			push_element(xml, 2, "itunes:title", &attributes.title);
+			push_element(xml, 2, "itunes:summary", &attributes.caption);
+			push_element(xml, 2, "itunes:duration", &attributes.length.to_string());
+			push_element(
+				xml,
+				2,
+				"itunes:season",
+				&attributes.season_number.to_string(),
+			);
+			push_element(xml, 2, "itunes:episode", &attributes.number.to_string());
+			push_element(xml, 2, "itunes:episodeType", "full");
+			if let Some(image) = thumbnail(episode) {
+				xml.push_str(&format!(
+					"\t\t<itunes:image href=\"{}\"/>\n",
+					escape(&image.attributes.large)
+				));
+			}
+		}
+
+		xml.push_str("\t</item>\n");
+	}
+
+	/// Renders the feed as an Atom document.
+	pub fn to_atom(&self) -> String {
+		let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+		xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+
+		push_element(&mut xml, 1, "id", &self.link);
+		push_element(&mut xml, 1, "title", &self.title);
+		push_element(&mut xml, 1, "subtitle", &self.description);
+		xml.push_str(&format!(
+			"\t<link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n",
+			escape(&self.link)
+		));
+		// Atom requires an updated element, an empty feed falls back to the unix epoch.
+		let updated = self
+			.updated()
+			.map(|updated| updated.to_rfc3339())
+			.unwrap_or_else(|| String::from("1970-01-01T00:00:00+00:00"));
+		push_element(&mut xml, 1, "updated", &updated);
+		push_element(&mut xml, 1, "generator", GENERATOR);
+		if let Some(podcast) = &self.podcast {
+			xml.push_str("\t<author>\n");
+			push_element(&mut xml, 2, "name", &podcast.author);
+			xml.push_str("\t</author>\n");
+		}
+
+		for episode in self.episodes {
+			let attributes = &episode.attributes;
+
+			xml.push_str("\t<entry>\n");
+			push_element(&mut xml, 2, "id", &format!("urn:uuid:{}", episode.uuid));
+			push_element(&mut xml, 2, "title", &attributes.title);
+			xml.push_str(&format!(
+				"\t\t<link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n",
+				escape(&episode_link(episode))
+			));
+			if let Some(media) = self.media.get(&episode.uuid) {
+				xml.push_str(&format!(
+					"\t\t<link rel=\"enclosure\" type=\"{}\" length=\"{}\" href=\"{}\"/>\n",
+					escape(&media.mime_type),
+					media.length,
+					escape(&media.url),
+				));
+			} else if let Some(image) = thumbnail(episode) {
+				xml.push_str(&format!(
+					"\t\t<link rel=\"enclosure\" type=\"{}\" href=\"{}\"/>\n",
+					mime_type(&image.attributes.large),
+					escape(&image.attributes.large),
+				));
+			}
+			push_element(
+				&mut xml,
+				2,
+				"published",
+				&attributes.public_golive_at.to_rfc3339(),
+			);
+			push_element(
+				&mut xml,
+				2,
+				"updated",
+				&attributes.public_golive_at.to_rfc3339(),
+			);
+			push_element(&mut xml, 2, "summary", &attributes.description);
+			for genre in &attributes.genres {
+				xml.push_str(&format!("\t\t<category term=\"{}\"/>\n", escape(genre)));
+			}
+			xml.push_str("\t\t<author>\n");
+			push_element(&mut xml, 3, "name", &attributes.show_title);
+			xml.push_str("\t\t</author>\n");
+			xml.push_str("\t</entry>\n");
+		}
+
+		xml.push_str("</feed>\n");
+
+		xml
+	}
+
+	/// The most recent public release in the feed.
+	fn updated(&self) -> Option<DateTime<FixedOffset>> {
+		self.episodes
+			.iter()
+			.map(|episode| episode.attributes.public_golive_at)
+			.max()
+	}
+}
+
+fn episode_link(episode: &Episode) -> String {
+	format!("{}{}", WEBSITE_URL, episode.canonical_links.reference)
+}
+
+/// Prefers the landscape thumbnail of an episode, falling back to whatever else is included.
+fn thumbnail(episode: &Episode) -> Option<&Image> {
+	let images = &episode.included.images;
+
+	best_image(images, "thumbnail", Some("landscape")).or_else(|| images.first())
+}
+
+fn mime_type(url: &str) -> &'static str {
+	let url = url.to_lowercase();
+
+	if url.ends_with(".png") {
+		"image/png"
+	} else if url.ends_with(".gif") {
+		"image/gif"
+	} else if url.ends_with(".webp") {
+		"image/webp"
+	} else {
+		"image/jpeg"
+	}
+}
+
+fn media_type(url: &str) -> &'static str {
+	let url = url.to_lowercase();
+	let path = url.split(['?', '#']).next().unwrap_or_default();
+
+	if path.ends_with(".mp3") {
+		"audio/mpeg"
+	} else if path.ends_with(".m4a") || path.ends_with(".aac") {
+		"audio/mp4"
+	} else if path.ends_with(".m3u8") {
+		"application/vnd.apple.mpegurl"
+	} else {
+		"video/mp4"
+	}
+}
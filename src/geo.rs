@@ -0,0 +1,192 @@
+/*!
+Geo-restrictions, based on the `blacklisted_countries` of episodes and series.
+
+Countries are ISO 3166-1 alpha-2 codes, the same format the API uses:
+
+```
+use roosterteeth_rs::geo::Country;
+
+let country: Country = "de".parse().unwrap();
+assert_eq!(country.to_string(), "DE");
+assert!("XX".parse::<Country>().is_err());
+```
+
+Setting a viewer country on the client filters the list endpoints down to what can be watched there:
+
+```no_run
+use roosterteeth_rs::requests::{Credential, Requests};
+
+let requests = Requests::new(Credential::Anonymous).with_country("AU".parse().unwrap());
+
+// Episodes blocked in Australia are left out.
+let episodes = requests.list_episodes(1, None, None);
+```
+*/
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::catalog::Catalog;
+use crate::search::Item;
+use crate::structs::episodes::Episode;
+use crate::structs::series::Series;
+
+// The officially assigned ISO 3166-1 alpha-2 codes, along with XK which is commonly used for Kosovo.
+const CODES: &str = "\
+	AD AE AF AG AI AL AM AO AQ AR AS AT AU AW AX AZ \
+	BA BB BD BE BF BG BH BI BJ BL BM BN BO BQ BR BS BT BV BW BY BZ \
+	CA CC CD CF CG CH CI CK CL CM CN CO CR CU CV CW CX CY CZ \
+	DE DJ DK DM DO DZ EC EE EG EH ER ES ET FI FJ FK FM FO FR \
+	GA GB GD GE GF GG GH GI GL GM GN GP GQ GR GS GT GU GW GY \
+	HK HM HN HR HT HU ID IE IL IM IN IO IQ IR IS IT JE JM JO JP \
+	KE KG KH KI KM KN KP KR KW KY KZ LA LB LC LI LK LR LS LT LU LV LY \
+	MA MC MD ME MF MG MH MK ML MM MN MO MP MQ MR MS MT MU MV MW MX MY MZ \
+	NA NC NE NF NG NI NL NO NP NR NU NZ OM PA PE PF PG PH PK PL PM PN PR PS PT PW PY \
+	QA RE RO RS RU RW SA SB SC SD SE SG SH SI SJ SK SL SM SN SO SR SS ST SV SX SY SZ \
+	TC TD TF TG TH TJ TK TL TM TN TO TR TT TV TW TZ UA UG UM US UY UZ \
+	VA VC VE VG VI VN VU WF WS XK YE YT ZA ZM ZW";
+
+/// An ISO 3166-1 alpha-2 country code, always uppercase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Country([u8; 2]);
+
+/// Returned when a string isn't an assigned country code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCountry(String);
+
+impl fmt::Display for InvalidCountry {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "\"{}\" is not an ISO 3166 country code", self.0)
+	}
+}
+
+impl error::Error for InvalidCountry {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		None
+	}
+}
+
+impl Country {
+	pub fn as_str(&self) -> &str {
+		// Only ever built from ascii letters.
+		std::str::from_utf8(&self.0).unwrap()
+	}
+}
+
+impl FromStr for Country {
+	type Err = InvalidCountry;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let code = s.trim().to_ascii_uppercase();
+
+		if code.len() == 2 && CODES.split_whitespace().any(|c| c == code) {
+			let bytes = code.as_bytes();
+			Ok(Country([bytes[0], bytes[1]]))
+		} else {
+			Err(InvalidCountry(s.to_string()))
+		}
+	}
+}
+
+impl fmt::Display for Country {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl TryFrom<String> for Country {
+	type Error = InvalidCountry;
+
+	fn try_from(s: String) -> Result<Self, Self::Error> {
+		s.parse()
+	}
+}
+
+impl From<Country> for String {
+	fn from(country: Country) -> String {
+		country.to_string()
+	}
+}
+
+/// Anything with a list of countries it can't be watched in.
+pub trait Restricted {
+	/// The raw `blacklisted_countries` returned by the API.
+	fn blacklisted_countries(&self) -> &[String];
+
+	/// The countries this is blocked in, ignoring any codes that aren't valid.
+	fn blocked_countries(&self) -> Vec<Country> {
+		self.blacklisted_countries()
+			.iter()
+			.filter_map(|code| code.parse().ok())
+			.collect()
+	}
+
+	fn is_viewable_in(&self, country: Country) -> bool {
+		!self.blocked_countries().contains(&country)
+	}
+}
+
+impl Restricted for Episode {
+	fn blacklisted_countries(&self) -> &[String] {
+		&self.attributes.blacklisted_countries
+	}
+}
+
+impl Restricted for Series {
+	fn blacklisted_countries(&self) -> &[String] {
+		&self.attributes.blacklisted_countries
+	}
+}
+
+/// Removes the items that are blocked in a country.
+pub fn viewable_in<T: Restricted>(items: Vec<T>, country: Country) -> Vec<T> {
+	items
+		.into_iter()
+		.filter(|item| item.is_viewable_in(country))
+		.collect()
+}
+
+/// A show or episode of a catalog which is blocked somewhere.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blocked<'a> {
+	pub item: Item<'a>,
+	pub countries: Vec<Country>,
+}
+
+impl Catalog {
+	/// Lists every show and episode blocked in at least one country, shows first, in catalog order.
+	pub fn blocked(&self) -> Vec<Blocked<'_>> {
+		let series = self
+			.series
+			.iter()
+			.map(|series| (Item::Series(series), series.blocked_countries()));
+		let episodes = self
+			.episodes
+			.iter()
+			.map(|episode| (Item::Episode(episode), episode.blocked_countries()));
+
+		series
+			.chain(episodes)
+			.filter(|(_, countries)| !countries.is_empty())
+			.map(|(item, countries)| Blocked { item, countries })
+			.collect()
+	}
+
+	/// Groups the blocked shows and episodes by the country they are blocked in.
+	pub fn blocked_by_country(&self) -> BTreeMap<Country, Vec<Item<'_>>> {
+		let mut report: BTreeMap<Country, Vec<Item>> = BTreeMap::new();
+
+		for blocked in self.blocked() {
+			for country in blocked.countries {
+				report.entry(country).or_default().push(blocked.item);
+			}
+		}
+
+		report
+	}
+}
@@ -0,0 +1,120 @@
+/*!
+Minimal parsing of the [HLS](https://tools.ietf.org/html/rfc8216) playlists returned in a video's url.
+
+The url of a [Video](../structs/videos/struct.Video.html) points at a master playlist, which lists the
+same stream in several qualities. Each of those variants has a media playlist listing the segments
+that make up the video, which can be downloaded and concatenated in order.
+*/
+
+use reqwest::Url;
+
+/// A single quality of a stream, as listed by a master playlist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+	/// Peak bits per second of the stream.
+	pub bandwidth: u64,
+	/// Width and height of the video, audio only streams don't have a resolution.
+	pub resolution: Option<(u32, u32)>,
+	pub codecs: Option<String>,
+	/// Absolute url of the variant's media playlist.
+	pub url: String,
+}
+
+impl Variant {
+	pub fn height(&self) -> Option<u32> {
+		self.resolution.map(|(_, height)| height)
+	}
+}
+
+/// Parses a master playlist, returning the variants sorted from lowest to highest bandwidth.
+/// Relative variant urls are resolved against `base`, the url the playlist was downloaded from.
+pub fn parse_master_playlist(base: &str, playlist: &str) -> Vec<Variant> {
+	let mut variants = Vec::new();
+	let mut stream_info: Option<&str> = None;
+
+	for line in playlist.lines().map(str::trim) {
+		if let Some(attributes) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+			stream_info = Some(attributes);
+		} else if !line.is_empty() && !line.starts_with('#') {
+			if let Some(attributes) = stream_info.take() {
+				let mut variant = Variant {
+					bandwidth: 0,
+					resolution: None,
+					codecs: None,
+					url: resolve(base, line),
+				};
+
+				for (key, value) in parse_attributes(attributes) {
+					match key {
+						"BANDWIDTH" => variant.bandwidth = value.parse().unwrap_or(0),
+						"RESOLUTION" => variant.resolution = parse_resolution(value),
+						"CODECS" => variant.codecs = Some(value.to_string()),
+						_ => {}
+					}
+				}
+
+				variants.push(variant);
+			}
+		}
+	}
+
+	variants.sort_by_key(|variant| variant.bandwidth);
+
+	variants
+}
+
+/// Parses a media playlist, returning the absolute urls of its segments in playback order.
+pub fn parse_media_playlist(base: &str, playlist: &str) -> Vec<String> {
+	playlist
+		.lines()
+		.map(str::trim)
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.map(|line| resolve(base, line))
+		.collect()
+}
+
+fn resolve(base: &str, url: &str) -> String {
+	match Url::parse(base).and_then(|base| base.join(url)) {
+		Ok(url) => url.to_string(),
+		Err(_) => url.to_string(),
+	}
+}
+
+fn parse_resolution(value: &str) -> Option<(u32, u32)> {
+	let mut dimensions = value.split('x');
+	let width = dimensions.next()?.parse().ok()?;
+	let height = dimensions.next()?.parse().ok()?;
+
+	Some((width, height))
+}
+
+/// Splits an attribute list such as `BANDWIDTH=1280000,CODECS="avc1.4d401f,mp4a.40.2"` into
+/// key value pairs, taking care not to split on commas inside quoted strings.
+fn parse_attributes(attributes: &str) -> Vec<(&str, &str)> {
+	let mut pairs = Vec::new();
+	let mut start = 0;
+	let mut quoted = false;
+
+	for (i, c) in attributes.char_indices() {
+		match c {
+			'"' => quoted = !quoted,
+			',' if !quoted => {
+				pairs.push(&attributes[start..i]);
+				start = i + 1;
+			}
+			_ => {}
+		}
+	}
+	pairs.push(&attributes[start..]);
+
+	pairs
+		.into_iter()
+		.filter_map(|pair| {
+			let mut split = pair.splitn(2, '=');
+			let key = split.next()?.trim();
+			let value = split.next()?.trim().trim_matches('"');
+
+			Some((key, value))
+		})
+		.collect()
+}
@@ -0,0 +1,325 @@
+/*!
+Plans where the episodes of a show are saved, following the folder and file naming Plex expects.
+
+```text
+Red vs. Blue (2003)/
+	Season 00/
+		Red vs. Blue (2003) - s00e01 - Behind the Scenes
+	Season 01/
+		Red vs. Blue (2003) - s01e01 - Why Are We Here
+		Red vs. Blue (2003) - s01e02 - Red Zeppelin Rules
+```
+
+Regular episodes keep the season and episode numbers of the API, while bonus features become specials in
+Season 00, numbered by release date. Shows that don't number their episodes reliably can end up with
+several episodes sharing a number, use [collisions](./struct.Plan.html#method.collisions) to find them and
+[renumber](./struct.Plan.html#method.renumber) to number each season sequentially instead.
+
+```no_run
+use roosterteeth_rs::layout::{Manifest, Plan};
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::structs::series::BonusFeature;
+
+let requests = Requests::new(Credential::Anonymous);
+let show = "red-vs-blue".parse().unwrap();
+
+let series = requests.get_series(&show);
+let episodes: Vec<_> = requests
+	.get_seasons(&show, Some("asc"))
+	.iter()
+	.flat_map(|season| requests.get_season_episodes(&season.attributes.slug, Some("asc")))
+	.collect();
+let bonus: Vec<_> = requests
+	.get_bonus_features(&series)
+	.into_iter()
+	.filter_map(BonusFeature::into_episode)
+	.collect();
+
+let mut plan = Plan::new(&series, &episodes, &bonus);
+if !plan.collisions().is_empty() {
+	plan.renumber();
+}
+// Episodes downloaded before stay where they are.
+if let Ok(previous) = Manifest::load("red-vs-blue.json") {
+	plan.keep_paths(&previous);
+}
+plan.manifest().save("red-vs-blue.json").unwrap();
+```
+*/
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::structs::episodes::Episode;
+use crate::structs::ids::{EpisodeSlug, ShowSlug};
+use crate::structs::series::Series;
+
+/// The season number Plex uses for specials.
+pub const SPECIALS: u16 = 0;
+
+/// Where a single episode is saved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry<'a> {
+	pub episode: &'a Episode,
+	pub season: u16,
+	pub number: u16,
+	/// Relative to the library, without an extension.
+	pub path: PathBuf,
+}
+
+impl<'a> Entry<'a> {
+	pub fn is_special(&self) -> bool {
+		self.season == SPECIALS
+	}
+}
+
+/// Several episodes that were planned with the same season and episode number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collision {
+	pub season: u16,
+	pub number: u16,
+	pub episodes: Vec<EpisodeSlug>,
+}
+
+/// The planned paths of a show's episodes, specials first and then in the order the show lists its seasons.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan<'a> {
+	pub show: ShowSlug,
+	/// The folder of the show, such as "Red vs. Blue (2003)".
+	pub show_dir: PathBuf,
+	pub entries: Vec<Entry<'a>>,
+}
+
+impl<'a> Plan<'a> {
+	/**
+	Plans the paths of a show's episodes and bonus features.
+
+	Seasons are ordered by number following the show's `season_order`, so shows that list their newest
+	season first are also planned, and downloaded, newest season first. Episodes within a season are ordered
+	by `sort_number`, which follows the show's `episode_order`, so episodes listed newest first are reversed
+	to be in release order. The order only affects the paths when the show is [renumbered](#method.renumber).
+	*/
+	pub fn new(series: &Series, episodes: &'a [Episode], bonus: &'a [Episode]) -> Self {
+		let attributes = &series.attributes;
+		let seasons_descending = attributes.season_order == "desc";
+		let descending = attributes.episode_order == "desc";
+
+		let year = episodes
+			.iter()
+			.map(|episode| episode.attributes.original_air_date)
+			.min()
+			.unwrap_or(attributes.published_at)
+			.format("%Y");
+		let show_dir = PathBuf::from(format!("{} ({})", sanitize(&attributes.title), year));
+
+		let mut regular: Vec<&Episode> = episodes.iter().collect();
+		regular.sort_by(|a, b| {
+			let season = a.attributes.season_number.cmp(&b.attributes.season_number);
+			let order = a.attributes.sort_number.cmp(&b.attributes.sort_number);
+
+			(if seasons_descending {
+				season.reverse()
+			} else {
+				season
+			})
+			.then(if descending { order.reverse() } else { order })
+		});
+
+		let mut specials: Vec<&Episode> = bonus.iter().collect();
+		specials.sort_by(|a, b| {
+			a.attributes
+				.original_air_date
+				.cmp(&b.attributes.original_air_date)
+				.then(a.attributes.sort_number.cmp(&b.attributes.sort_number))
+		});
+
+		let mut plan = Plan {
+			show: attributes.slug.clone(),
+			show_dir,
+			entries: Vec::new(),
+		};
+
+		for (index, episode) in specials.into_iter().enumerate() {
+			plan.push(episode, SPECIALS, index as u16 + 1);
+		}
+		for episode in regular {
+			plan.push(
+				episode,
+				episode.attributes.season_number,
+				episode.attributes.number,
+			);
+		}
+
+		plan
+	}
+
+	fn push(&mut self, episode: &'a Episode, season: u16, number: u16) {
+		let path = self.path_for(episode, season, number);
+
+		self.entries.push(Entry {
+			episode,
+			season,
+			number,
+			path,
+		});
+	}
+
+	fn path_for(&self, episode: &Episode, season: u16, number: u16) -> PathBuf {
+		let show_name = self.show_dir.to_string_lossy();
+
+		self.show_dir
+			.join(format!("Season {:02}", season))
+			.join(format!(
+				"{} - s{:02}e{:02} - {}",
+				show_name,
+				season,
+				number,
+				sanitize(&episode.attributes.title)
+			))
+	}
+
+	/// Lists the season and episode numbers used by more than one episode.
+	pub fn collisions(&self) -> Vec<Collision> {
+		let mut numbers: BTreeMap<(u16, u16), Vec<EpisodeSlug>> = BTreeMap::new();
+
+		for entry in &self.entries {
+			numbers
+				.entry((entry.season, entry.number))
+				.or_default()
+				.push(entry.episode.attributes.slug.clone());
+		}
+
+		numbers
+			.into_iter()
+			.filter(|(_, episodes)| episodes.len() > 1)
+			.map(|((season, number), episodes)| Collision {
+				season,
+				number,
+				episodes,
+			})
+			.collect()
+	}
+
+	/// Numbers the episodes of each season from 1 in the planned order, replacing the numbers of the API.
+	pub fn renumber(&mut self) {
+		let mut season = None;
+		let mut number = 0;
+
+		for index in 0..self.entries.len() {
+			let entry = &self.entries[index];
+			if season != Some(entry.season) {
+				season = Some(entry.season);
+				number = 0;
+			}
+			number += 1;
+
+			let path = self.path_for(entry.episode, entry.season, number);
+			let entry = &mut self.entries[index];
+			entry.number = number;
+			entry.path = path;
+		}
+	}
+
+	/// Keeps the season, number and path a previous manifest of the show gave each episode, so that downloaded
+	/// episodes stay where they are when the API renumbers them. Episodes new to the manifest are left as planned,
+	/// so check [collisions](#method.collisions) afterwards in case they now share a number with a kept episode.
+	pub fn keep_paths(&mut self, manifest: &Manifest) {
+		if manifest.show != self.show {
+			return;
+		}
+
+		for entry in &mut self.entries {
+			let slug = &entry.episode.attributes.slug;
+
+			if let Some(saved) = manifest.entries.iter().find(|saved| &saved.slug == slug) {
+				entry.season = saved.season;
+				entry.number = saved.number;
+				entry.path = saved.path.clone();
+			}
+		}
+	}
+
+	pub fn path_of(&self, slug: &EpisodeSlug) -> Option<&Path> {
+		self.entries
+			.iter()
+			.find(|entry| &entry.episode.attributes.slug == slug)
+			.map(|entry| entry.path.as_path())
+	}
+
+	pub fn manifest(&self) -> Manifest {
+		Manifest {
+			show: self.show.clone(),
+			show_dir: self.show_dir.clone(),
+			entries: self
+				.entries
+				.iter()
+				.map(|entry| ManifestEntry {
+					slug: entry.episode.attributes.slug.clone(),
+					season: entry.season,
+					number: entry.number,
+					path: entry.path.clone(),
+				})
+				.collect(),
+			collisions: self.collisions(),
+		}
+	}
+}
+
+/// A saved [Plan](./struct.Plan.html), so later downloads of the show use the same paths.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+	pub show: ShowSlug,
+	pub show_dir: PathBuf,
+	pub entries: Vec<ManifestEntry>,
+	/// The numbers used by more than one episode of the saved plan.
+	pub collisions: Vec<Collision>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+	pub slug: EpisodeSlug,
+	pub season: u16,
+	pub number: u16,
+	pub path: PathBuf,
+}
+
+impl Manifest {
+	/// Reads a manifest previously written with [save](#method.save).
+	pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Manifest> {
+		let reader = BufReader::new(File::open(path)?);
+
+		serde_json::from_reader(reader).map_err(io::Error::from)
+	}
+
+	/// Writes the manifest as json.
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let mut writer = BufWriter::new(File::create(path)?);
+
+		serde_json::to_writer_pretty(&mut writer, self)?;
+		writer.flush()
+	}
+
+	pub fn path_of(&self, slug: &EpisodeSlug) -> Option<&Path> {
+		self.entries
+			.iter()
+			.find(|entry| &entry.slug == slug)
+			.map(|entry| entry.path.as_path())
+	}
+}
+
+/// Strips a value of characters that aren't valid in file names on common platforms.
+pub fn sanitize(value: &str) -> String {
+	value
+		.chars()
+		.map(|c| match c {
+			'/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+			_ => c,
+		})
+		.collect::<String>()
+		.trim()
+		.trim_end_matches('.')
+		.to_string()
+}
@@ -18,17 +18,52 @@ println!("{}",episodes[0].attributes.title);
 All of the returned values are documented in the following page as structs:
 * [Channels](./structs/channels/struct.Channel.html)
 * [Episodes](./structs/episodes/struct.Episode.html)
+* [Featured Items](./structs/featured_items/struct.FeaturedItem.html)
+* [Livestreams](./structs/livestreams/struct.Livestream.html)
+* [Products](./structs/products/struct.Product.html)
 * [Seasons](./structs/seasons/struct.Season.html)
 * [Series](./structs/series/struct.Series.html)
+* [Users](./structs/users/struct.User.html)
 * [Videos](./structs/videos/struct.Video.html)
 
+Images shared by all of the above are described in the [common](./structs/common/index.html) module, and the
+ids, uuids and slugs of each resource are typed in the [ids](./structs/ids/index.html) module.
+
 Please note the difference between an [Episode](./structs/episode/struct.Episode.html) and a [Video](./structs/video/struct.Video.html) struct.
 An episode struct returns all the information about that episode, while a video struct is useful mainly for getting the m3u8 urls and
 will fail if you don't have permissions to watch the video. (For example, if you aren't a first member or the video isn't public.)
+
+The [schedule](./schedule/index.html) module can be used to find out when an episode goes live for each membership tier,
+and the [access](./access/index.html) module checks whether a user can watch it before calling `get_video()`.
 */
 
+// The crate is formatted with hard tabs, which includes the examples in doc comments.
+#![allow(clippy::tabs_in_doc_comments)]
+
+pub mod access;
+pub mod archive;
+pub mod browse;
+pub mod calendar;
+pub mod catalog;
+pub mod diff;
+pub mod export;
+pub mod feed;
+pub mod geo;
+pub mod hls;
+pub mod layout;
+pub mod nfo;
+pub mod pages;
 pub mod requests;
+pub mod resource;
+pub mod schedule;
+pub mod search;
+#[cfg(feature = "serve")]
+pub mod serve;
 pub mod structs;
+#[cfg(feature = "tracing")]
+mod trace;
+pub mod transport;
+mod xml;
 
 #[cfg(test)]
 mod tests;
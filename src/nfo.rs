@@ -0,0 +1,336 @@
+/*!
+Writes the NFO metadata and artwork Kodi and Jellyfin use to import downloaded shows.
+
+A show is laid out as follows, where each episode's path is chosen by the caller to match its video file:
+
+```text
+Red vs. Blue/
+	tvshow.nfo
+	poster.jpg
+	fanart.jpg
+	Season 01/
+		season.nfo
+		poster.jpg
+		S01E01 - Why Are We Here.ts
+		S01E01 - Why Are We Here.nfo
+		S01E01 - Why Are We Here-thumb.jpg
+```
+
+If episodes aren't kept in season folders, the season metadata is written next to `tvshow.nfo`
+as `season01.nfo` and `season01-poster.jpg` instead.
+
+```no_run
+use roosterteeth_rs::nfo;
+use roosterteeth_rs::requests::{Credential, Requests};
+use std::path::Path;
+
+let requests = Requests::new(Credential::Anonymous);
+let show = "red-vs-blue".parse().unwrap();
+
+let series = requests.get_series(&show);
+let seasons = requests.get_seasons(&show, Some("asc"));
+let episodes: Vec<_> = seasons
+	.iter()
+	.flat_map(|season| requests.get_season_episodes(&season.attributes.slug, Some("asc")))
+	.collect();
+let channel = requests
+	.list_channels()
+	.into_iter()
+	.find(|channel| channel.attributes.slug == series.attributes.channel_slug);
+
+let dir = Path::new("Red vs. Blue");
+nfo::export(&requests, dir, &series, &seasons, &episodes, channel.as_ref(), |episode| {
+	dir.join(format!("Season {:02}", episode.attributes.season_number))
+		.join(format!("S{:02}E{:02}", episode.attributes.season_number, episode.attributes.number))
+})
+.unwrap();
+```
+*/
+
+use chrono::offset::FixedOffset;
+use chrono::DateTime;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::requests::Requests;
+use crate::structs::channels::Channel;
+use crate::structs::common::{best_image, Image, Size};
+use crate::structs::episodes::{CastMember, Episode};
+use crate::structs::seasons::Season;
+use crate::structs::series::Series;
+use crate::xml::{escape, push_element};
+
+const HEADER: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+const UNIQUE_ID: &str = "roosterteeth";
+
+/// An image to download, along with the name of the file it should be saved as without an extension.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Artwork<'a> {
+	pub name: String,
+	pub image: &'a Image,
+}
+
+impl<'a> Artwork<'a> {
+	/// The file name including the extension of the original image, which is usually jpg.
+	pub fn file_name(&self) -> String {
+		let url = self.image.attributes.url(Size::Large).to_lowercase();
+		let extension = if url.ends_with(".png") {
+			"png"
+		} else if url.ends_with(".webp") {
+			"webp"
+		} else {
+			"jpg"
+		};
+
+		format!("{}.{}", self.name, extension)
+	}
+}
+
+/// Renders the `tvshow.nfo` of a show, with the cast collected from its episodes.
+/// The channel is used as the studio.
+pub fn tvshow(series: &Series, episodes: &[Episode], studio: Option<&Channel>) -> String {
+	let attributes = &series.attributes;
+	let mut xml = String::from(HEADER);
+
+	xml.push_str("<tvshow>\n");
+	push_element(&mut xml, 1, "title", &attributes.title);
+	push_element(&mut xml, 1, "plot", &attributes.summary);
+	push_element(&mut xml, 1, "premiered", &date(&attributes.published_at));
+	push_genres(&mut xml, &attributes.genres);
+	push_studio(&mut xml, studio);
+	push_unique_id(&mut xml, attributes.slug.as_str());
+
+	let mut cast: Vec<&CastMember> = Vec::new();
+	for member in episodes
+		.iter()
+		.flat_map(|episode| &episode.included.cast_members)
+	{
+		if !cast.iter().any(|m| m.id == member.id) {
+			cast.push(member);
+		}
+	}
+	push_cast(&mut xml, &cast);
+
+	xml.push_str("</tvshow>\n");
+
+	xml
+}
+
+/// Renders the `season.nfo` of a season.
+pub fn season(season: &Season) -> String {
+	let attributes = &season.attributes;
+	let mut xml = String::from(HEADER);
+
+	xml.push_str("<season>\n");
+	push_element(&mut xml, 1, "title", &attributes.title);
+	push_element(&mut xml, 1, "plot", &attributes.description);
+	push_element(&mut xml, 1, "seasonnumber", &attributes.number.to_string());
+	push_element(&mut xml, 1, "premiered", &date(&attributes.published_at));
+	push_unique_id(&mut xml, attributes.slug.as_str());
+	xml.push_str("</season>\n");
+
+	xml
+}
+
+/// Renders the nfo of an episode, which is saved next to its video with the same name.
+/// The channel is used as the studio.
+pub fn episode(episode: &Episode, studio: Option<&Channel>) -> String {
+	let attributes = &episode.attributes;
+	let mut xml = String::from(HEADER);
+
+	xml.push_str("<episodedetails>\n");
+	push_element(&mut xml, 1, "title", &attributes.title);
+	push_element(&mut xml, 1, "showtitle", &attributes.show_title);
+	push_element(&mut xml, 1, "season", &attributes.season_number.to_string());
+	push_element(&mut xml, 1, "episode", &attributes.number.to_string());
+	push_element(&mut xml, 1, "plot", &attributes.description);
+	push_element(&mut xml, 1, "aired", &date(&attributes.original_air_date));
+	// Kodi expects whole minutes.
+	push_element(
+		&mut xml,
+		1,
+		"runtime",
+		&attributes.length.div_ceil(60).to_string(),
+	);
+	push_genres(&mut xml, &attributes.genres);
+	push_studio(&mut xml, studio);
+	push_unique_id(&mut xml, attributes.slug.as_str());
+	push_cast(
+		&mut xml,
+		&episode.included.cast_members.iter().collect::<Vec<_>>(),
+	);
+	xml.push_str("</episodedetails>\n");
+
+	xml
+}
+
+/// The poster and fanart of a show.
+pub fn series_artwork(series: &Series) -> Vec<Artwork<'_>> {
+	let images = &series.included.images;
+	let mut artwork = Vec::new();
+
+	if let Some(image) = best_image(images, "poster", Some("portrait")) {
+		artwork.push(Artwork {
+			name: String::from("poster"),
+			image,
+		});
+	}
+	if let Some(image) = best_image(images, "cover", Some("landscape")) {
+		artwork.push(Artwork {
+			name: String::from("fanart"),
+			image,
+		});
+	}
+
+	artwork
+}
+
+/// The poster of a season, named with a prefix such as "season01-" when it isn't in its own folder.
+pub fn season_artwork<'a>(season: &'a Season, prefix: &str) -> Vec<Artwork<'a>> {
+	let images = &season.included.images;
+
+	best_image(images, "poster", Some("portrait"))
+		.or_else(|| images.first())
+		.map(|image| Artwork {
+			name: format!("{}poster", prefix),
+			image,
+		})
+		.into_iter()
+		.collect()
+}
+
+/// The thumbnail of an episode, named after the episode's file such as "S01E01-thumb".
+pub fn episode_artwork<'a>(episode: &'a Episode, file_stem: &str) -> Vec<Artwork<'a>> {
+	let images = &episode.included.images;
+
+	best_image(images, "thumbnail", Some("landscape"))
+		.or_else(|| images.first())
+		.map(|image| Artwork {
+			name: format!("{}-thumb", file_stem),
+			image,
+		})
+		.into_iter()
+		.collect()
+}
+
+/// Downloads artwork into a directory, skipping images that already exist.
+/// Images that fail to download return an error without leaving a file behind, so they are retried next time.
+pub fn write_artwork(requests: &Requests, dir: &Path, artwork: &[Artwork]) -> io::Result<()> {
+	for art in artwork {
+		let path = dir.join(art.file_name());
+
+		if !path.exists() {
+			let image = requests
+				.get_image(art.image, Size::Large)
+				.map_err(io::Error::other)?;
+
+			// Written to a temporary file first, so an interrupted write isn't mistaken for the image later.
+			let partial = path.with_extension("partial");
+			fs::write(&partial, image)?;
+			fs::rename(&partial, &path)?;
+		}
+	}
+
+	Ok(())
+}
+
+/**
+Writes the metadata and artwork of a show into `dir`.
+
+`episode_path` returns where an episode is saved, without its extension, so that its nfo and thumbnail
+can be saved alongside. Season metadata is written to the folder of a season's episodes when that
+differs from `dir`.
+*/
+pub fn export<F>(
+	requests: &Requests,
+	dir: &Path,
+	series: &Series,
+	seasons: &[Season],
+	episodes: &[Episode],
+	studio: Option<&Channel>,
+	episode_path: F,
+) -> io::Result<()>
+where
+	F: Fn(&Episode) -> PathBuf,
+{
+	fs::create_dir_all(dir)?;
+	fs::write(dir.join("tvshow.nfo"), tvshow(series, episodes, studio))?;
+	write_artwork(requests, dir, &series_artwork(series))?;
+
+	for season in seasons {
+		let folder = episodes
+			.iter()
+			.find(|episode| episode.attributes.season_slug == season.attributes.slug)
+			.and_then(|episode| episode_path(episode).parent().map(Path::to_path_buf))
+			.filter(|folder| folder != dir);
+
+		match folder {
+			Some(folder) => {
+				fs::create_dir_all(&folder)?;
+				fs::write(folder.join("season.nfo"), self::season(season))?;
+				write_artwork(requests, &folder, &season_artwork(season, ""))?;
+			}
+			None => {
+				let prefix = format!("season{:02}", season.attributes.number);
+				fs::write(dir.join(format!("{}.nfo", prefix)), self::season(season))?;
+				write_artwork(
+					requests,
+					dir,
+					&season_artwork(season, &format!("{}-", prefix)),
+				)?;
+			}
+		}
+	}
+
+	for episode in episodes {
+		let path = episode_path(episode);
+		let folder = path.parent().unwrap_or(dir);
+		let stem = path
+			.file_name()
+			.map(|name| name.to_string_lossy().into_owned())
+			.unwrap_or_default();
+
+		fs::create_dir_all(folder)?;
+		fs::write(
+			folder.join(format!("{}.nfo", stem)),
+			self::episode(episode, studio),
+		)?;
+		write_artwork(requests, folder, &episode_artwork(episode, &stem))?;
+	}
+
+	Ok(())
+}
+
+fn date(date: &DateTime<FixedOffset>) -> String {
+	date.format("%Y-%m-%d").to_string()
+}
+
+fn push_genres(xml: &mut String, genres: &[String]) {
+	for genre in genres {
+		push_element(xml, 1, "genre", genre);
+	}
+}
+
+fn push_studio(xml: &mut String, studio: Option<&Channel>) {
+	if let Some(channel) = studio {
+		push_element(xml, 1, "studio", &channel.attributes.name);
+	}
+}
+
+fn push_unique_id(xml: &mut String, slug: &str) {
+	xml.push_str(&format!(
+		"\t<uniqueid type=\"{}\" default=\"true\">{}</uniqueid>\n",
+		UNIQUE_ID,
+		escape(slug)
+	));
+}
+
+fn push_cast(xml: &mut String, cast: &[&CastMember]) {
+	for (order, member) in cast.iter().enumerate() {
+		xml.push_str("\t<actor>\n");
+		push_element(xml, 2, "name", &member.attributes.name);
+		push_element(xml, 2, "order", &order.to_string());
+		xml.push_str("\t</actor>\n");
+	}
+}
@@ -0,0 +1,66 @@
+/*!
+Iterators over paginated endpoints, which only fetch the next page once the current one has been consumed.
+
+```no_run
+use roosterteeth_rs::requests::{Credential, Requests};
+
+let requests = Requests::new(Credential::Anonymous);
+
+// Only a single page of episodes is held in memory at a time.
+for episode in requests.iter_episodes(None, Some("asc")) {
+	println!("{}", episode.attributes.title);
+}
+```
+*/
+
+use std::vec;
+
+/// Yields the items of each page in turn, stopping at the first empty page.
+pub struct Pages<'a, T> {
+	fetch: Box<dyn FnMut(u16) -> Vec<T> + 'a>,
+	page: u16,
+	items: vec::IntoIter<T>,
+	done: bool,
+}
+
+impl<'a, T> Pages<'a, T> {
+	/// Creates an iterator calling `fetch` with page numbers starting at 1.
+	pub fn new<F>(fetch: F) -> Self
+	where
+		F: FnMut(u16) -> Vec<T> + 'a,
+	{
+		Pages {
+			fetch: Box::new(fetch),
+			page: 0,
+			items: Vec::new().into_iter(),
+			done: false,
+		}
+	}
+
+	/// The number of pages fetched so far.
+	pub fn pages_fetched(&self) -> u16 {
+		self.page
+	}
+}
+
+impl<'a, T> Iterator for Pages<'a, T> {
+	type Item = T;
+
+	fn next(&mut self) -> Option<T> {
+		loop {
+			if let Some(item) = self.items.next() {
+				return Some(item);
+			}
+			if self.done {
+				return None;
+			}
+
+			self.page += 1;
+			let items = (self.fetch)(self.page);
+			if items.is_empty() {
+				self.done = true;
+			}
+			self.items = items.into_iter();
+		}
+	}
+}
@@ -1,52 +1,105 @@
-use reqwest::blocking::Client;
-use reqwest::header::HeaderMap;
+use chrono::Utc;
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use std::error;
 use std::fmt;
-
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::access;
+use crate::archive::Archive;
+use crate::catalog::Catalog;
+use crate::geo::{self, Country, Restricted};
+use crate::hls;
+use crate::pages::Pages;
+use crate::resource::{Resolved, Resource, UrlError};
+use crate::search;
+use crate::structs::ids::*;
 use crate::structs::*;
+#[cfg(feature = "tracing")]
+use crate::trace;
+use crate::transport::{Http, Recorder, Response, Transport};
+
+const USER_AGENT: &str = "Mozilla/5.0 roosterteeth-rs/0.3.1 reqwest/0.11";
 
-const USER_AGENT: &'static str = "Mozilla/5.0 roosterteeth-rs/0.3.1 reqwest/0.11";
+const LOGIN_URL: &str = "https://auth.roosterteeth.com/oauth/token";
+const BUSINESS_URL: &str = "https://business-service.roosterteeth.com/api/v1";
+const API_URL: &str = "https://svod-be.roosterteeth.com/api/v1";
 
-const LOGIN_URL: &'static str = "https://auth.roosterteeth.com/oauth/token";
-const API_URL: &'static str = "https://svod-be.roosterteeth.com/api/v1";
+pub use crate::access::VideoUnavailable;
 
 type Result<T> = std::result::Result<T, VideoUnavailable>;
 
-#[derive(Debug, Clone)]
-pub struct VideoUnavailable;
+/// Returned by the user endpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserError {
+	/// The client was created with `Credential::Anonymous`.
+	NotLoggedIn,
+	/// The api answered with an error status, such as 401 once the token has expired or 404 for an unknown episode.
+	Http(u16),
+}
+
+impl fmt::Display for UserError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			UserError::NotLoggedIn => write!(f, "This requires logging in with Credential::Login."),
+			UserError::Http(status) => write!(f, "The request failed with status {}.", status),
+		}
+	}
+}
+
+impl error::Error for UserError {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		None
+	}
+}
+
+/// Returned by get_image() when the image couldn't be downloaded, such as a 404 for a removed image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageUnavailable {
+	pub url: String,
+	pub status: u16,
+}
 
-impl fmt::Display for VideoUnavailable {
+impl fmt::Display for ImageUnavailable {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "Video is not (yet?) avaliable for non-sponsors.")
+		write!(
+			f,
+			"The image {} failed to download with status {}.",
+			self.url, self.status
+		)
 	}
 }
 
-impl error::Error for VideoUnavailable {
+impl error::Error for ImageUnavailable {
 	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
 		None
 	}
 }
 
-fn append_channel_and_order<'a>(
-	url: &mut String,
-	channel: Option<&'a str>,
-	order: Option<&'a str>,
-) {
-	if channel != None {
+/// The data of any paginated response, for endpoints that return several kinds of item.
+#[derive(Deserialize)]
+struct Page<T> {
+	data: Vec<T>,
+	total_pages: Option<u16>,
+}
+
+fn append_channel_and_order(url: &mut String, channel: Option<&ChannelSlug>, order: Option<&str>) {
+	if let Some(channel) = channel {
 		url.push_str("&channel_id=");
-		url.push_str(channel.unwrap());
+		url.push_str(channel.as_str());
 	}
 
 	url.push_str("&order=");
-	if order == None {
-		url.push_str("desc");
-	} else {
-		url.push_str(order.unwrap());
-	}
+	url.push_str(order.unwrap_or("desc"));
 }
 
-#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+#[derive(Deserialize)]
 struct Token {
 	access_token: String,
 	token_type: String,
@@ -54,8 +107,23 @@ struct Token {
 	refresh_token: String,
 	scope: String,
 	created_at: u32,
-	user_id: u32,
-	uuid: String,
+	user_id: UserId,
+	uuid: UserUuid,
+}
+
+// Written by hand so that the tokens can't end up in logs.
+impl fmt::Debug for Token {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Token")
+			.field("access_token", &"[redacted]")
+			.field("token_type", &self.token_type)
+			.field("expires_in", &self.expires_in)
+			.field("refresh_token", &"[redacted]")
+			.field("scope", &self.scope)
+			.field("user_id", &self.user_id)
+			.field("uuid", &self.uuid)
+			.finish()
+	}
 }
 
 pub enum Credential<'a> {
@@ -70,6 +138,24 @@ pub struct Requests {
 
 	// Used for storing bearer: <token> in case of authenticated download
 	headers: HeaderMap,
+
+	// The logged in user, whose uuid is part of the watchlist and watch progress urls
+	user: Option<UserUuid>,
+
+	// Lists of episodes and series only include what can be watched here
+	country: Option<Country>,
+
+	// The svod api, which is replaced by a local server in tests
+	api_url: String,
+
+	// Where raw responses are kept when archiving
+	archive: Option<Archive>,
+
+	// The last response that couldn't be archived, until it is taken
+	archive_error: Mutex<Option<io::Error>>,
+
+	// Sends every request, normally over the network
+	transport: Box<dyn Transport>,
 }
 
 impl Requests {
@@ -80,14 +166,35 @@ impl Requests {
 			.build()
 			.expect("Unable to build the reqwest client");
 
+		Requests::login(client.clone(), Box::new(Http::new(client)), credential)
+	}
+
+	/// Like [new](#method.new), but logs in through another transport as well, so that logged in
+	/// sessions can be recorded with a [Recorder](../transport/struct.Recorder.html) and replayed later.
+	pub fn new_with_transport<T: Transport + 'static>(
+		credential: Credential,
+		transport: T,
+	) -> Self {
+		let client = Client::builder()
+			.user_agent(USER_AGENT)
+			.build()
+			.expect("Unable to build the reqwest client");
+
+		Requests::login(client, Box::new(transport), credential)
+	}
+
+	fn login(client: Client, transport: Box<dyn Transport>, credential: Credential) -> Self {
 		let login: Option<(&str, &str)> = match credential {
 			Credential::Anonymous => None,
 			Credential::Login(u, p) => Some((u, p)),
 		};
 
 		let mut headers = HeaderMap::new();
-		if login.is_some() {
-			let (user, pass) = login.unwrap();
+		let mut uuid = None;
+		if let Some((user, pass)) = login {
+			// The password and token are never recorded.
+			#[cfg(feature = "tracing")]
+			let _span = tracing::info_span!("login").entered();
 
 			let body = format!(
 				"{{\"client_id\":\"4338d2b4bdc8db1239360f28e72f0d9ddb1fd01e7a38fbb07b4b1f4ba4564cc5\",\"grant_type\":\"password\",\"password\":\"{}\",\"scope\":\"user public\",\"username\":\"{}\"}}",
@@ -95,31 +202,200 @@ impl Requests {
 				user,
 			);
 
-			let token: Token = client
-				.post(LOGIN_URL)
-				.body(body)
-				.send()
-				.unwrap()
-				.json()
-				.unwrap();
-
-			headers.insert(
-				"authorization",
-				format!("Bearer {}", token.access_token).parse().unwrap(),
-			);
+			// Sent straight to the transport rather than through fetch, so the token is never archived.
+			let request = client.post(LOGIN_URL).body(body).build().unwrap();
+			let response = transport.send(request).unwrap();
+			let token: Token = serde_json::from_slice(&response.body).unwrap();
+
+			// Sensitive values are left out of Debug output.
+			let mut authorization: HeaderValue =
+				format!("Bearer {}", token.access_token).parse().unwrap();
+			authorization.set_sensitive(true);
+			headers.insert("authorization", authorization);
+
+			#[cfg(feature = "tracing")]
+			tracing::info!(user = %token.uuid, "logged in");
+			uuid = Some(token.uuid);
 		}
 
 		Requests {
-			client: client,
-			headers: headers,
+			client,
+			headers,
+			user: uuid,
+			country: None,
+			api_url: API_URL.to_string(),
+			archive: None,
+			archive_error: Mutex::new(None),
+			transport,
+		}
+	}
+
+	/// Sets the country the viewer is in, so that episodes and series blocked there are left out of lists.
+	/// Catalogs synced with a country set will only contain what can be watched there.
+	pub fn with_country(mut self, country: Country) -> Self {
+		self.country = Some(country);
+		self
+	}
+
+	pub fn country(&self) -> Option<Country> {
+		self.country
+	}
+
+	/// Sends catalog requests to another server with the same api, such as a local [mirror](../serve/index.html).
+	/// The url includes the version, for example `http://127.0.0.1:8080/api/v1`.
+	/// Logging in and the profile endpoint still use the RoosterTeeth servers.
+	pub fn with_api_url(mut self, url: &str) -> Self {
+		self.api_url = url.trim_end_matches('/').to_string();
+		self
+	}
+
+	pub fn api_url(&self) -> &str {
+		&self.api_url
+	}
+
+	// Links in responses are relative to the host rather than the api
+	fn api_host(&self) -> &str {
+		self.api_url.trim_end_matches("/api/v1")
+	}
+
+	fn viewable<T: Restricted>(&self, items: Vec<T>) -> Vec<T> {
+		match self.country {
+			Some(country) => geo::viewable_in(items, country),
+			None => items,
+		}
+	}
+
+	fn is_viewable<T: Restricted>(&self, item: &T) -> bool {
+		self.country
+			.is_none_or(|country| item.is_viewable_in(country))
+	}
+
+	/// Archives every response to a directory, so the raw json can be recovered later.
+	/// Responses that fail to archive don't stop the request, the error is kept for
+	/// [take_archive_error](#method.take_archive_error) and logged when the `tracing` feature is enabled.
+	pub fn with_archive(mut self, archive: Archive) -> Self {
+		self.archive = Some(archive);
+		self
+	}
+
+	pub fn archive(&self) -> Option<&Archive> {
+		self.archive.as_ref()
+	}
+
+	/// The error of the last response that couldn't be archived, if any, clearing it.
+	pub fn take_archive_error(&self) -> Option<io::Error> {
+		self.archive_error.lock().unwrap().take()
+	}
+
+	/// Sends requests through another transport, such as a [Replayer](../transport/struct.Replayer.html)
+	/// for tests. Logging in has already happened by then, use [new_with_transport](#method.new_with_transport)
+	/// to send it through the transport too.
+	pub fn with_transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+		self.transport = Box::new(transport);
+		self
+	}
+
+	/// Saves every request and response to a cassette file, which can be replayed later.
+	pub fn recording<P: AsRef<Path>>(mut self, cassette: P) -> Self {
+		let inner = std::mem::replace(
+			&mut self.transport,
+			Box::new(Http::new(self.client.clone())),
+		);
+		self.transport = Box::new(Recorder::new(inner, cassette));
+		self
+	}
+
+	/// Sends a request and reads the response into memory, archiving it if an archive is set.
+	fn fetch(&self, request: RequestBuilder) -> Response {
+		let request = request.build().unwrap();
+		let method = request.method().to_string();
+		let url = request.url().to_string();
+
+		#[cfg(feature = "tracing")]
+		let call = trace::Call::start(&request, &self.api_url);
+
+		let result = self.transport.send(request);
+
+		#[cfg(feature = "tracing")]
+		call.finish(&result);
+
+		let response = result.unwrap();
+
+		if let Some(archive) = &self.archive {
+			let stored = archive.store(
+				&method,
+				&url,
+				response.status,
+				response.headers.clone(),
+				&response.body,
+			);
+			if let Err(e) = stored {
+				#[cfg(feature = "tracing")]
+				tracing::warn!(
+					url = %trace::redact_url(&url.parse().unwrap()),
+					error = %e,
+					"unable to archive response"
+				);
+				*self.archive_error.lock().unwrap() = Some(e);
+			}
+		}
+
+		response
+	}
+
+	fn json<T: DeserializeOwned>(&self, request: RequestBuilder) -> T {
+		serde_json::from_slice(&self.fetch(request).body).unwrap()
+	}
+
+	fn user(&self) -> std::result::Result<&UserUuid, UserError> {
+		self.user.as_ref().ok_or(UserError::NotLoggedIn)
+	}
+
+	/// Sends a request with the bearer token, turning error statuses into `UserError::Http`.
+	fn user_fetch(&self, request: RequestBuilder) -> std::result::Result<Response, UserError> {
+		let response = self.fetch(request.headers(self.headers.clone()));
+
+		if response.is_success() {
+			Ok(response)
+		} else {
+			Err(UserError::Http(response.status))
+		}
+	}
+
+	/// Fetches every page of a user endpoint, stopping at the first request that fails.
+	fn user_pages<T: DeserializeOwned>(&self, url: &str) -> std::result::Result<Vec<T>, UserError> {
+		let mut error = None;
+		let items = Pages::new(|page| {
+			let request = self
+				.client
+				.get(url)
+				.query(&[("per_page", "100"), ("page", &page.to_string())]);
+
+			match self.user_fetch(request) {
+				Ok(response) => {
+					serde_json::from_slice::<Page<T>>(&response.body)
+						.unwrap()
+						.data
+				}
+				Err(e) => {
+					error = Some(e);
+					Vec::new()
+				}
+			}
+		})
+		.collect();
+
+		match error {
+			Some(e) => Err(e),
+			None => Ok(items),
 		}
 	}
 
 	/// Returns a list of avaliable RoosterTeeth channels that can be used by the API.
 	pub fn list_channels(&self) -> Vec<channels::Channel> {
-		let url = format!("{}/channels", API_URL);
+		let url = format!("{}/channels", self.api_url);
 
-		let result: channels::Root = self.client.get(&url).send().unwrap().json().unwrap();
+		let result: channels::Root = self.json(self.client.get(&url));
 
 		result.data
 	}
@@ -129,46 +405,89 @@ impl Requests {
 	pub fn list_episodes<'a>(
 		&self,
 		page: u16,
-		channel: Option<&'a str>,
+		channel: Option<&'a ChannelSlug>,
+		order: Option<&'a str>,
+	) -> Vec<episodes::Episode> {
+		self.viewable(self.episodes_page(page, channel, order))
+	}
+
+	/// Iterates over every episode, fetching a page of 100 at a time as the iterator is consumed.
+	pub fn iter_episodes<'a>(
+		&'a self,
+		channel: Option<&'a ChannelSlug>,
 		order: Option<&'a str>,
+	) -> impl Iterator<Item = episodes::Episode> + 'a {
+		Pages::new(move |page| self.episodes_page(page, channel, order))
+			.filter(move |episode| self.is_viewable(episode))
+	}
+
+	fn episodes_page(
+		&self,
+		page: u16,
+		channel: Option<&ChannelSlug>,
+		order: Option<&str>,
 	) -> Vec<episodes::Episode> {
-		let mut url = format!("{}/episodes?per_page=100", API_URL);
+		let mut url = format!("{}/episodes?per_page=100", self.api_url);
 
 		append_channel_and_order(&mut url, channel, order);
 
 		url = format!("{}&page={}", url, page);
 
-		let result: episodes::Root = self.client.get(&url).send().unwrap().json().unwrap();
+		let result: episodes::Root = self.json(self.client.get(&url));
 
 		result.data
 	}
 
 	pub fn list_series<'a>(
 		&self,
-		channel: Option<&'a str>,
+		channel: Option<&'a ChannelSlug>,
+		order: Option<&'a str>,
+	) -> Vec<series::Series> {
+		self.viewable(self.series_page(1, 1000, channel, order))
+	}
+
+	/// Iterates over every series, fetching a page of 100 at a time as the iterator is consumed.
+	pub fn iter_series<'a>(
+		&'a self,
+		channel: Option<&'a ChannelSlug>,
 		order: Option<&'a str>,
+	) -> impl Iterator<Item = series::Series> + 'a {
+		Pages::new(move |page| self.series_page(page, 100, channel, order))
+			.filter(move |series| self.is_viewable(series))
+	}
+
+	fn series_page(
+		&self,
+		page: u16,
+		per_page: u16,
+		channel: Option<&ChannelSlug>,
+		order: Option<&str>,
 	) -> Vec<series::Series> {
-		let mut url = format!("{}/shows?per_page=1000", API_URL);
+		let mut url = format!("{}/shows?per_page={}", self.api_url, per_page);
 
 		append_channel_and_order(&mut url, channel, order);
 
-		url.push_str("&page=1");
+		url = format!("{}&page={}", url, page);
 
-		let result: series::Root = self.client.get(&url).send().unwrap().json().unwrap();
+		let result: series::Root = self.json(self.client.get(&url));
 
 		result.data
 	}
 
 	/// Gets all season information from a specific series from its slug.
-	pub fn get_seasons<'a>(&self, slug: &'a str, order: Option<&'a str>) -> Vec<seasons::Season> {
+	pub fn get_seasons<'a>(
+		&self,
+		slug: &'a ShowSlug,
+		order: Option<&'a str>,
+	) -> Vec<seasons::Season> {
 		let url = format!(
 			"{}/shows/{}/seasons?order={}",
-			API_URL,
+			self.api_url,
 			slug,
 			order.unwrap_or("desc"),
 		);
 
-		let result: seasons::Root = self.client.get(&url).send().unwrap().json().unwrap();
+		let result: seasons::Root = self.json(self.client.get(&url));
 
 		result.data
 	}
@@ -176,59 +495,422 @@ impl Requests {
 	/// Gets the episodes belonging to a specific season by its slug.
 	pub fn get_season_episodes<'a>(
 		&self,
-		slug: &'a str,
+		slug: &'a SeasonSlug,
 		order: Option<&'a str>,
+	) -> Vec<episodes::Episode> {
+		self.viewable(self.season_episodes_page(1, slug, order))
+	}
+
+	/// Iterates over the episodes of a season, for seasons with more than the 100 episodes of a single page.
+	pub fn iter_season_episodes<'a>(
+		&'a self,
+		slug: &'a SeasonSlug,
+		order: Option<&'a str>,
+	) -> impl Iterator<Item = episodes::Episode> + 'a {
+		Pages::new(move |page| self.season_episodes_page(page, slug, order))
+			.filter(move |episode| self.is_viewable(episode))
+	}
+
+	fn season_episodes_page(
+		&self,
+		page: u16,
+		slug: &SeasonSlug,
+		order: Option<&str>,
 	) -> Vec<episodes::Episode> {
 		let url = format!(
-			"{}/seasons/{}/episodes?order={}&per_page=100",
-			API_URL,
+			"{}/seasons/{}/episodes?order={}&per_page=100&page={}",
+			self.api_url,
 			slug,
 			order.unwrap_or("asc"),
+			page,
 		);
 
-		let result: episodes::Root = self.client.get(&url).send().unwrap().json().unwrap();
+		let result: episodes::Root = self.json(self.client.get(&url));
 
 		result.data
 	}
 
 	/// Gets a specific series information from its slug.
 	/// This returns an identical result to those of list_series()
-	pub fn get_series<'a>(&self, slug: &'a str) -> series::Series {
-		let url = format!("{}/shows/{}", API_URL, slug);
+	pub fn get_series(&self, slug: &ShowSlug) -> series::Series {
+		self.find_series(slug)
+			.unwrap_or_else(|| panic!("The show {} does not exist", slug))
+	}
 
-		let mut result: series::Root = self.client.get(&url).send().unwrap().json().unwrap();
+	/// Like get_series(), but returns None for a show that doesn't exist.
+	pub fn find_series(&self, slug: &ShowSlug) -> Option<series::Series> {
+		let url = format!("{}/shows/{}", self.api_url, slug);
 
-		result.data.remove(0)
+		self.first(self.client.get(&url))
 	}
 
-	pub fn get_episode<'a>(&self, slug: &'a str) -> episodes::Episode {
-		let url = format!("{}/watch/{}", API_URL, slug);
+	pub fn get_episode(&self, slug: &EpisodeSlug) -> episodes::Episode {
+		self.find_episode(slug)
+			.unwrap_or_else(|| panic!("The episode {} does not exist", slug))
+	}
 
-		let mut result: episodes::Root = self.client.get(&url).send().unwrap().json().unwrap();
+	/// Like get_episode(), but returns None for an episode that doesn't exist.
+	pub fn find_episode(&self, slug: &EpisodeSlug) -> Option<episodes::Episode> {
+		let url = format!("{}/watch/{}", self.api_url, slug);
 
-		result.data.remove(0)
+		self.first(self.client.get(&url))
+	}
+
+	/// The first item of a response, or None if the request failed or the response is empty.
+	fn first<T: DeserializeOwned>(&self, request: RequestBuilder) -> Option<T> {
+		let response = self.fetch(request);
+		if !response.is_success() {
+			return None;
+		}
+
+		let result: Page<T> = serde_json::from_slice(&response.body).unwrap();
+
+		result.data.into_iter().next()
 	}
 
 	/// Gets an episodes viewing information from its slug.
 	/// Please note that this can result in an error if we don't have the permission to
-	/// view that video.
-	pub fn get_video<'a>(&self, slug: &'a str) -> Result<videos::Video> {
-		let url = format!("{}/watch/{}/videos", API_URL, slug);
-
-		let response = self
-			.client
-			.get(&url)
-			// This consumes the header, and HeaderMap doesn't implement Copy
-			.headers(self.headers.clone())
-			.send()
-			.unwrap();
+	/// view that video, [can_watch](../access/fn.can_watch.html) checks this beforehand.
+	pub fn get_video(&self, slug: &EpisodeSlug) -> Result<videos::Video> {
+		let url = format!("{}/watch/{}/videos", self.api_url, slug);
+
+		let response = self.fetch(
+			self.client
+				.get(&url)
+				// This consumes the header, and HeaderMap doesn't implement Copy
+				.headers(self.headers.clone()),
+		);
 
-		if response.status().is_success() {
-			let mut result: videos::Root = response.json().unwrap();
+		if response.is_success() {
+			let mut result: videos::Root = serde_json::from_slice(&response.body).unwrap();
 
 			Ok(result.data.remove(0))
 		} else {
-			Err(VideoUnavailable)
+			Err(access::denial(
+				StatusCode::from_u16(response.status).unwrap(),
+				&String::from_utf8_lossy(&response.body),
+			))
+		}
+	}
+
+	/// Gets the bonus features of a show, such as behind the scenes videos and commentaries, by following
+	/// its `bonus_features` link.
+	pub fn get_bonus_features(&self, series: &series::Series) -> Vec<series::BonusFeature> {
+		self.link_pages(&series.links.bonus_features)
+			.filter(|feature| match feature {
+				series::BonusFeature::Episode(episode) => self.is_viewable(episode.as_ref()),
+				_ => true,
+			})
+			.collect()
+	}
+
+	/// Gets the shows RoosterTeeth recommends to viewers of a show, following its `related` link.
+	pub fn get_related_shows(&self, series: &series::Series) -> Vec<series::Series> {
+		self.link_pages(&series.links.related)
+			.filter(|series| self.is_viewable(series))
+			.collect()
+	}
+
+	/// Gets the shows recommended alongside an episode, following its `related_shows` link.
+	pub fn get_episode_related_shows(&self, episode: &episodes::Episode) -> Vec<series::Series> {
+		self.link_pages(&episode.links.related_shows)
+			.filter(|series| self.is_viewable(series))
+			.collect()
+	}
+
+	/// Iterates over the pages of a link from a response, such as `/api/v1/shows/red-vs-blue/related`.
+	fn link_pages<'a, T: DeserializeOwned + 'a>(&'a self, link: &str) -> Pages<'a, T> {
+		let url = format!("{}{}", self.api_host(), link);
+		let mut last = false;
+		let mut previous = Vec::new();
+
+		Pages::new(move |page| {
+			if last {
+				return Vec::new();
+			}
+
+			let body = self
+				.fetch(
+					self.client
+						.get(&url)
+						.query(&[("per_page", "100"), ("page", &page.to_string())]),
+				)
+				.body;
+			// Some of these endpoints ignore the page and answer every request with the first one.
+			if body == previous {
+				return Vec::new();
+			}
+
+			let result: Page<T> = serde_json::from_slice(&body).unwrap();
+			last = match result.total_pages {
+				Some(total) => page >= total,
+				None => result.data.len() < 100,
+			};
+			previous = body;
+
+			result.data
+		})
+	}
+
+	/// Lists the livestreams of a channel, including ones that aren't currently live.
+	/// Like get_livestream(), sponsor only streams will not have a url unless we are logged in.
+	pub fn list_livestreams(&self, channel: &ChannelSlug) -> Vec<livestreams::Livestream> {
+		let url = format!("{}/channels/{}/livestreams", self.api_url, channel);
+
+		let result: livestreams::Root =
+			self.json(self.client.get(&url).headers(self.headers.clone()));
+
+		result.data
+	}
+
+	/// Gets a specific livestream from its slug.
+	/// Sponsor only streams will not have a url unless we are logged in.
+	pub fn get_livestream(&self, slug: &LivestreamSlug) -> livestreams::Livestream {
+		let url = format!("{}/livestreams/{}", self.api_url, slug);
+
+		let mut result: livestreams::Root =
+			self.json(self.client.get(&url).headers(self.headers.clone()));
+
+		result.data.remove(0)
+	}
+
+	/// Lists the promotions currently featured on a channel's page.
+	pub fn list_featured_items(&self, channel: &ChannelSlug) -> Vec<featured_items::FeaturedItem> {
+		let url = format!("{}/channels/{}/featured_items", self.api_url, channel);
+
+		let result: featured_items::Root = self.json(self.client.get(&url));
+
+		result.data
+	}
+
+	/// Gets the merchandise collections linked to a channel.
+	pub fn get_channel_product_collections(
+		&self,
+		channel: &ChannelSlug,
+	) -> Vec<products::ProductCollection> {
+		let url = format!("{}/channels/{}/product_collections", self.api_url, channel);
+
+		let result: products::CollectionRoot = self.json(self.client.get(&url));
+
+		result.data
+	}
+
+	/// Gets the merchandise collections linked to a show.
+	pub fn get_series_product_collections(
+		&self,
+		slug: &ShowSlug,
+	) -> Vec<products::ProductCollection> {
+		let url = format!("{}/shows/{}/product_collections", self.api_url, slug);
+
+		let result: products::CollectionRoot = self.json(self.client.get(&url));
+
+		result.data
+	}
+
+	/// Gets the merchandise linked to an episode.
+	pub fn get_episode_products(&self, slug: &EpisodeSlug) -> Vec<products::Product> {
+		let url = format!("{}/watch/{}/products", self.api_url, slug);
+
+		let result: products::ProductRoot = self.json(self.client.get(&url));
+
+		result.data
+	}
+
+	/// Gets the profile of the logged in user, including their membership tier.
+	pub fn get_profile(&self) -> std::result::Result<users::User, UserError> {
+		self.user()?;
+		let url = format!("{}/me", BUSINESS_URL);
+
+		let response = self.user_fetch(self.client.get(&url))?;
+		let mut result: users::Root = serde_json::from_slice(&response.body).unwrap();
+
+		Ok(result.data.remove(0))
+	}
+
+	/// Gets the episodes the logged in user has queued up in their watchlist, in the order they were added.
+	pub fn get_watchlist(&self) -> std::result::Result<Vec<episodes::Episode>, UserError> {
+		let url = format!("{}/users/{}/watchlist", self.api_url, self.user()?);
+
+		Ok(self.viewable(self.user_pages(&url)?))
+	}
+
+	/// Adds an episode to the end of the logged in user's watchlist.
+	pub fn add_to_watchlist(&self, episode: &EpisodeUuid) -> std::result::Result<(), UserError> {
+		let url = format!("{}/users/{}/watchlist", self.api_url, self.user()?);
+
+		self.user_fetch(
+			self.client
+				.post(&url)
+				.json(&serde_json::json!({ "episode_id": episode })),
+		)?;
+
+		Ok(())
+	}
+
+	/// Removes an episode from the logged in user's watchlist.
+	pub fn remove_from_watchlist(
+		&self,
+		episode: &EpisodeUuid,
+	) -> std::result::Result<(), UserError> {
+		let url = format!(
+			"{}/users/{}/watchlist/{}",
+			self.api_url,
+			self.user()?,
+			episode
+		);
+
+		self.user_fetch(self.client.delete(&url))?;
+
+		Ok(())
+	}
+
+	/// Gets how far the logged in user has watched an episode, or None if they haven't started it.
+	pub fn get_watch_progress(
+		&self,
+		episode: &EpisodeUuid,
+	) -> std::result::Result<Option<users::WatchProgress>, UserError> {
+		let url = format!("{}/users/{}/watch_times", self.api_url, self.user()?);
+
+		let response = self.user_fetch(
+			self.client
+				.get(&url)
+				.query(&[("episode_ids", episode.to_string())]),
+		)?;
+		let mut result: users::ProgressRoot = serde_json::from_slice(&response.body).unwrap();
+
+		Ok(result.data.pop())
+	}
+
+	/// Gets the logged in user's progress in every episode they have started.
+	pub fn list_watch_progress(&self) -> std::result::Result<Vec<users::WatchProgress>, UserError> {
+		let url = format!("{}/users/{}/watch_times", self.api_url, self.user()?);
+
+		self.user_pages(&url)
+	}
+
+	/// Saves the logged in user's position in an episode, in seconds.
+	pub fn set_watch_progress(
+		&self,
+		episode: &EpisodeUuid,
+		seconds: u32,
+	) -> std::result::Result<(), UserError> {
+		let url = format!(
+			"{}/users/{}/watch_times/{}",
+			self.api_url,
+			self.user()?,
+			episode
+		);
+
+		self.user_fetch(
+			self.client
+				.put(&url)
+				.json(&serde_json::json!({ "value": seconds })),
+		)?;
+
+		Ok(())
+	}
+
+	/// Searches the catalog for shows, seasons and episodes matching the query.
+	/// Pages start at 1, and contain up to 100 results.
+	pub fn search(&self, query: &str, kind: search::Kind, page: u16) -> Vec<search::SearchResult> {
+		let url = format!("{}/search", self.api_url);
+
+		let result: search::Root = self.json(self.client.get(&url).query(&[
+			("query", query),
+			("types", kind.as_param()),
+			("page", &page.to_string()),
+			("per_page", "100"),
+		]));
+
+		result.data
+	}
+
+	/// Downloads every channel, along with the shows, seasons and episodes of a channel or of all channels.
+	/// This makes a request for every show and season, so it can take a while.
+	pub fn sync_catalog(&self, channel: Option<&ChannelSlug>) -> Catalog {
+		let channels = self.list_channels();
+		let series: Vec<series::Series> = self.iter_series(channel, Some("asc")).collect();
+
+		let mut seasons = Vec::new();
+		let mut episodes = Vec::new();
+		for show in &series {
+			for season in self.get_seasons(&show.attributes.slug, Some("asc")) {
+				episodes.extend(self.iter_season_episodes(&season.attributes.slug, Some("asc")));
+				seasons.push(season);
+			}
+		}
+
+		Catalog {
+			synced_at: Utc::now(),
+			channels,
+			series,
+			seasons,
+			episodes,
+		}
+	}
+
+	/// Fetches the episode, series, season or channel that a roosterteeth.com link refers to.
+	/// See the [resource](../resource/index.html) module for the supported links.
+	pub fn resolve(&self, url: &str) -> std::result::Result<Resolved, UrlError> {
+		let not_found = || UrlError::NotFound(url.to_string());
+
+		match Resource::from_url(url)? {
+			Resource::Episode(slug) => self
+				.find_episode(&slug)
+				.map(Resolved::Episode)
+				.ok_or_else(not_found),
+			Resource::Series(slug) => self
+				.find_series(&slug)
+				.map(Resolved::Series)
+				.ok_or_else(not_found),
+			Resource::Season { show, number } => self
+				.get_seasons(&show, None)
+				.into_iter()
+				.find(|season| season.attributes.number == number)
+				.map(Resolved::Season)
+				.ok_or_else(not_found),
+			Resource::Channel(slug) => self
+				.list_channels()
+				.into_iter()
+				.find(|channel| channel.attributes.slug == slug)
+				.map(Resolved::Channel)
+				.ok_or_else(not_found),
+		}
+	}
+
+	/// Downloads the master playlist of a video, returning its variants from lowest to highest quality.
+	pub fn get_hls_variants(&self, video: &videos::Video) -> Vec<hls::Variant> {
+		let url = &video.attributes.url;
+
+		let playlist = self.fetch(self.client.get(url)).text();
+
+		hls::parse_master_playlist(url, &playlist)
+	}
+
+	/// Downloads the media playlist of a variant, returning the urls of its segments in order.
+	pub fn get_hls_segments(&self, variant: &hls::Variant) -> Vec<String> {
+		let playlist = self.fetch(self.client.get(&variant.url)).text();
+
+		hls::parse_media_playlist(&variant.url, &playlist)
+	}
+
+	/// Downloads an image in the given size, returning the raw bytes of the file.
+	pub fn get_image(
+		&self,
+		image: &common::Image,
+		size: common::Size,
+	) -> std::result::Result<Vec<u8>, ImageUnavailable> {
+		let url = image.attributes.url(size);
+
+		let response = self.fetch(self.client.get(url));
+
+		if response.is_success() {
+			Ok(response.body)
+		} else {
+			Err(ImageUnavailable {
+				url: url.to_string(),
+				status: response.status,
+			})
 		}
 	}
 }
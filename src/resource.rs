@@ -0,0 +1,136 @@
+/*!
+Recognises links to the roosterteeth.com website, so they can be resolved to API calls.
+
+The following urls are understood, with or without the domain:
+* `/watch/<episode>`, as in an episode's [CanonicalLinks](../structs/episodes/struct.CanonicalLinks.html)
+* `/series/<show>`, as in a series' [CanonicalLinks](../structs/series/struct.CanonicalLinks.html)
+* `/series/<show>?season=<number>`, a specific season of a show
+* `/channel/<channel>`
+
+The scheme is optional, so `roosterteeth.com/series/red-vs-blue` is also accepted.
+
+```
+use roosterteeth_rs::resource::Resource;
+
+let resource = Resource::from_url("https://roosterteeth.com/watch/red-vs-blue-season-1-episode-1").unwrap();
+
+match resource {
+	Resource::Episode(slug) => assert_eq!(slug, "red-vs-blue-season-1-episode-1"),
+	_ => unreachable!(),
+}
+```
+
+Use [Requests::resolve](../requests/struct.Requests.html#method.resolve) to fetch the resource a link refers to.
+*/
+
+use reqwest::Url;
+use std::error;
+use std::fmt;
+
+use crate::structs::ids::{ChannelSlug, EpisodeSlug, ShowSlug};
+use crate::structs::*;
+
+const WEBSITE_URL: &str = "https://roosterteeth.com";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resource {
+	Episode(EpisodeSlug),
+	Series(ShowSlug),
+	Season { show: ShowSlug, number: u16 },
+	Channel(ChannelSlug),
+}
+
+/// The typed object a [Resource](./enum.Resource.html) was resolved to.
+// Only ever returned one at a time, so the size difference between variants doesn't matter.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolved {
+	Episode(episodes::Episode),
+	Series(series::Series),
+	Season(seasons::Season),
+	Channel(channels::Channel),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+	/// The url isn't a roosterteeth.com link, or isn't one of the supported pages.
+	Unrecognized(String),
+	/// The url was understood, but the resource it refers to doesn't exist.
+	NotFound(String),
+}
+
+impl fmt::Display for UrlError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			UrlError::Unrecognized(url) => {
+				write!(f, "{} is not a recognised RoosterTeeth link", url)
+			}
+			UrlError::NotFound(url) => write!(f, "{} does not exist", url),
+		}
+	}
+}
+
+impl error::Error for UrlError {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		None
+	}
+}
+
+impl Resource {
+	/// Parses a roosterteeth.com link, or a path on the website such as "/series/red-vs-blue".
+	pub fn from_url(url: &str) -> Result<Resource, UrlError> {
+		let unrecognized = || UrlError::Unrecognized(url.to_string());
+
+		// Links are often copied without the scheme, which would otherwise be treated as a relative path.
+		let trimmed = url.trim();
+		let absolute = if trimmed.starts_with('/') || trimmed.contains("://") {
+			trimmed.to_string()
+		} else {
+			format!("https://{}", trimmed)
+		};
+
+		let base = Url::parse(WEBSITE_URL).unwrap();
+		let parsed = base.join(&absolute).map_err(|_| unrecognized())?;
+
+		let host = parsed.host_str().unwrap_or_default();
+		if host != "roosterteeth.com" && !host.ends_with(".roosterteeth.com") {
+			return Err(unrecognized());
+		}
+
+		let segments: Vec<&str> = parsed
+			.path_segments()
+			.map(|segments| segments.filter(|s| !s.is_empty()).collect())
+			.unwrap_or_default();
+
+		let season = parsed
+			.query_pairs()
+			.find(|(key, _)| key == "season")
+			.and_then(|(_, value)| value.parse::<u16>().ok());
+
+		match segments.as_slice() {
+			["watch", slug] => Ok(Resource::Episode(slug.parse().map_err(|_| unrecognized())?)),
+			["series", slug] => {
+				let show = slug.parse().map_err(|_| unrecognized())?;
+
+				Ok(match season {
+					Some(number) => Resource::Season { show, number },
+					None => Resource::Series(show),
+				})
+			}
+			["channel", slug] => Ok(Resource::Channel(slug.parse().map_err(|_| unrecognized())?)),
+			_ => Err(unrecognized()),
+		}
+	}
+
+	/// The canonical link to this resource on the website.
+	pub fn url(&self) -> String {
+		match self {
+			Resource::Episode(slug) => format!("{}/watch/{}", WEBSITE_URL, slug),
+			Resource::Series(slug) => format!("{}/series/{}", WEBSITE_URL, slug),
+			Resource::Season { show, number } => {
+				format!("{}/series/{}?season={}", WEBSITE_URL, show, number)
+			}
+			Resource::Channel(slug) => format!("{}/channel/{}", WEBSITE_URL, slug),
+		}
+	}
+}
@@ -0,0 +1,177 @@
+/*!
+Episodes are released in waves: sponsors (FIRST members) usually get an episode first, followed
+by site members and finally the public. This module reasons about those go-live times.
+
+```no_run
+use chrono::Utc;
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::schedule::{Schedule, Tier};
+
+let requests = Requests::new(Credential::Anonymous);
+
+// Upcoming releases for a channel, the same works for get_season_episodes() on a show.
+let episodes = requests.list_episodes(1, Some(&"rooster-teeth".parse().unwrap()), None);
+let schedule = Schedule::upcoming(&episodes, Utc::now());
+
+for event in schedule.for_tier(Tier::Public) {
+	println!("{} goes live at {}", event.episode.attributes.title, event.at);
+}
+```
+*/
+
+use chrono::offset::FixedOffset;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::structs::episodes::Episode;
+use crate::structs::videos::Video;
+
+/// The audience an episode is released to, in order of least to most privileged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tier {
+	/// Anyone, including anonymous visitors.
+	Public,
+	/// Logged in users without a paid membership.
+	Member,
+	/// Paying FIRST members.
+	Sponsor,
+}
+
+impl Tier {
+	/// All tiers, from least to most privileged.
+	pub const ALL: [Tier; 3] = [Tier::Public, Tier::Member, Tier::Sponsor];
+}
+
+impl fmt::Display for Tier {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Tier::Public => write!(f, "public"),
+			Tier::Member => write!(f, "member"),
+			Tier::Sponsor => write!(f, "sponsor"),
+		}
+	}
+}
+
+/// Whether an episode can be watched by a tier at a given point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Availability {
+	Available,
+	/// The episode will go live for this tier at the given time.
+	Upcoming(DateTime<FixedOffset>),
+	/// The episode will never be released to this tier, for example sponsor only content.
+	Unavailable,
+}
+
+impl Episode {
+	/// Returns the time the episode goes live for a tier, regardless of whether that tier can view it.
+	pub fn golive_for(&self, tier: Tier) -> DateTime<FixedOffset> {
+		match tier {
+			Tier::Public => self.attributes.public_golive_at,
+			Tier::Member => self.attributes.member_golive_at,
+			Tier::Sponsor => self.attributes.sponsor_golive_at,
+		}
+	}
+
+	/// Determines whether the episode can be watched by `tier` at the time `now`.
+	pub fn availability_for(&self, tier: Tier, now: DateTime<Utc>) -> Availability {
+		availability(
+			self.attributes.is_sponsors_only,
+			self.golive_for(tier),
+			tier,
+			now,
+		)
+	}
+}
+
+impl Video {
+	/// Returns the time the video goes live for a tier, regardless of whether that tier can view it.
+	pub fn golive_for(&self, tier: Tier) -> DateTime<FixedOffset> {
+		match tier {
+			Tier::Public => self.attributes.public_golive_at,
+			Tier::Member => self.attributes.member_golive_at,
+			Tier::Sponsor => self.attributes.sponsor_golive_at,
+		}
+	}
+
+	/// Determines whether the video can be watched by `tier` at the time `now`.
+	pub fn availability_for(&self, tier: Tier, now: DateTime<Utc>) -> Availability {
+		availability(
+			self.attributes.is_sponsors_only,
+			self.golive_for(tier),
+			tier,
+			now,
+		)
+	}
+}
+
+fn availability(
+	is_sponsors_only: bool,
+	golive: DateTime<FixedOffset>,
+	tier: Tier,
+	now: DateTime<Utc>,
+) -> Availability {
+	if is_sponsors_only && tier != Tier::Sponsor {
+		Availability::Unavailable
+	} else if golive <= now {
+		Availability::Available
+	} else {
+		Availability::Upcoming(golive)
+	}
+}
+
+/// A single release of an episode to a tier.
+#[derive(Debug, Clone, Copy)]
+pub struct GoLive<'a> {
+	pub tier: Tier,
+	pub at: DateTime<FixedOffset>,
+	pub episode: &'a Episode,
+}
+
+/// A chronological list of go-live events, built from episodes of a channel or show.
+#[derive(Debug, Clone)]
+pub struct Schedule<'a> {
+	events: Vec<GoLive<'a>>,
+}
+
+impl<'a> Schedule<'a> {
+	/// Collects every go-live event that happens after `now`, sorted by time.
+	/// Events are ordered by tier when several happen at the same time.
+	pub fn upcoming(episodes: &'a [Episode], now: DateTime<Utc>) -> Self {
+		let mut events: Vec<GoLive<'a>> = episodes
+			.iter()
+			.flat_map(|episode| {
+				Tier::ALL.iter().filter_map(move |&tier| {
+					match episode.availability_for(tier, now) {
+						Availability::Upcoming(at) => Some(GoLive { tier, at, episode }),
+						_ => None,
+					}
+				})
+			})
+			.collect();
+
+		events.sort_by(|a, b| a.at.cmp(&b.at).then(a.tier.cmp(&b.tier)));
+
+		Schedule { events }
+	}
+
+	/// All events in chronological order.
+	pub fn events(&self) -> &[GoLive<'a>] {
+		&self.events
+	}
+
+	/// The events releasing to a single tier, in chronological order.
+	pub fn for_tier(&self, tier: Tier) -> impl Iterator<Item = &GoLive<'a>> {
+		self.events.iter().filter(move |event| event.tier == tier)
+	}
+
+	/// The next event to happen, if there is one.
+	pub fn next(&self) -> Option<&GoLive<'a>> {
+		self.events.first()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.events.is_empty()
+	}
+}
@@ -0,0 +1,262 @@
+/*!
+Searching shows, seasons and episodes, either through the API or offline in a synced [Catalog](../catalog/struct.Catalog.html).
+
+```no_run
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::search::{Kind, SearchResult};
+
+let requests = Requests::new(Credential::Anonymous);
+
+for result in requests.search("red vs blue", Kind::All, 1) {
+	match result {
+		SearchResult::Series(series) => println!("Show: {}", series.attributes.title),
+		SearchResult::Season(season) => println!("Season: {}", season.attributes.title),
+		SearchResult::Episode(episode) => println!("Episode: {}", episode.attributes.title),
+		SearchResult::Other(_) => {}
+	}
+}
+```
+
+The offline search matches every word of the query against titles, captions, descriptions,
+tags and cast names, ranking results by where the words were found.
+*/
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::catalog::Catalog;
+use crate::structs::*;
+
+/// Restricts a search to one kind of result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+	All,
+	Shows,
+	Seasons,
+	Episodes,
+}
+
+impl Kind {
+	/// The value of the API's `types` parameter.
+	pub(crate) fn as_param(self) -> &'static str {
+		match self {
+			Kind::All => "shows,seasons,episodes",
+			Kind::Shows => "shows",
+			Kind::Seasons => "seasons",
+			Kind::Episodes => "episodes",
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Root {
+	pub data: Vec<SearchResult>,
+	pub page: Option<u16>,
+	pub per_page: Option<u16>,
+	pub total_pages: Option<u16>,
+	pub total_results: Option<u32>,
+}
+
+/// The search endpoint mixes shows, seasons and episodes in a single list.
+/// Results are told apart by their `type`, and anything unexpected is kept as `Other` rather than failing the page.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum SearchResult {
+	Episode(Box<episodes::Episode>),
+	Season(Box<seasons::Season>),
+	Series(Box<series::Series>),
+	Other(Value),
+}
+
+impl<'de> Deserialize<'de> for SearchResult {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let value = Value::deserialize(deserializer)?;
+
+		let parsed = match value.get("type").and_then(Value::as_str) {
+			Some("episode") | Some("bonus_feature") => {
+				serde_json::from_value(value.clone()).map(SearchResult::Episode)
+			}
+			Some("season") => serde_json::from_value(value.clone()).map(SearchResult::Season),
+			Some("show") => serde_json::from_value(value.clone()).map(SearchResult::Series),
+			// Every result has a type, including the ones serialized by this crate, so there's no guessing by shape.
+			_ => return Ok(SearchResult::Other(value)),
+		};
+
+		Ok(parsed.unwrap_or(SearchResult::Other(value)))
+	}
+}
+
+/// A show, season or episode found in a catalog.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Item<'a> {
+	Series(&'a series::Series),
+	Season(&'a seasons::Season),
+	Episode(&'a episodes::Episode),
+}
+
+impl<'a> Item<'a> {
+	pub fn title(&self) -> &'a str {
+		match self {
+			Item::Series(series) => &series.attributes.title,
+			Item::Season(season) => &season.attributes.title,
+			Item::Episode(episode) => &episode.attributes.title,
+		}
+	}
+}
+
+/// A search result along with its relevance, higher is better.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit<'a> {
+	pub score: u32,
+	pub item: Item<'a>,
+}
+
+// How much a matching word counts towards the score, depending on the field it was found in.
+const TITLE: u32 = 10;
+const CAPTION: u32 = 5;
+const TAG: u32 = 4;
+const CAST: u32 = 4;
+const SHOW_TITLE: u32 = 3;
+const GENRE: u32 = 2;
+const DESCRIPTION: u32 = 1;
+
+impl Catalog {
+	/// Searches the catalog, returning the results that contain every word of the query, most relevant first.
+	pub fn search(&self, query: &str, kind: Kind) -> Vec<Hit<'_>> {
+		let terms = words(query);
+		if terms.is_empty() {
+			return Vec::new();
+		}
+		let phrase = terms.join(" ");
+
+		let mut hits = Vec::new();
+
+		if kind == Kind::All || kind == Kind::Shows {
+			for series in &self.series {
+				let attributes = &series.attributes;
+				let mut fields = vec![
+					(TITLE, attributes.title.clone()),
+					(DESCRIPTION, attributes.summary.clone()),
+				];
+				fields.extend(attributes.genres.iter().map(|genre| (GENRE, genre.clone())));
+
+				if let Some(score) = score(&terms, &phrase, &fields) {
+					hits.push(Hit {
+						score,
+						item: Item::Series(series),
+					});
+				}
+			}
+		}
+
+		if kind == Kind::All || kind == Kind::Seasons {
+			for season in &self.seasons {
+				let attributes = &season.attributes;
+				let show_title = self
+					.find_series(&attributes.show_slug)
+					.map(|series| series.attributes.title.clone())
+					.unwrap_or_else(|| attributes.show_slug.to_string());
+				let fields = vec![
+					(TITLE, attributes.title.clone()),
+					(SHOW_TITLE, show_title),
+					(DESCRIPTION, attributes.description.clone()),
+				];
+
+				if let Some(score) = score(&terms, &phrase, &fields) {
+					hits.push(Hit {
+						score,
+						item: Item::Season(season),
+					});
+				}
+			}
+		}
+
+		if kind == Kind::All || kind == Kind::Episodes {
+			for episode in &self.episodes {
+				let attributes = &episode.attributes;
+				let included = &episode.included;
+				let mut fields = vec![
+					(TITLE, attributes.title.clone()),
+					(CAPTION, attributes.caption.clone()),
+					(SHOW_TITLE, attributes.show_title.clone()),
+					(DESCRIPTION, attributes.description.clone()),
+				];
+				fields.extend(
+					included
+						.tags
+						.iter()
+						.map(|tag| (TAG, tag.attributes.tag.clone())),
+				);
+				fields.extend(
+					included
+						.cast_members
+						.iter()
+						.map(|member| (CAST, member.attributes.name.clone())),
+				);
+				fields.extend(attributes.genres.iter().map(|genre| (GENRE, genre.clone())));
+
+				if let Some(score) = score(&terms, &phrase, &fields) {
+					hits.push(Hit {
+						score,
+						item: Item::Episode(episode),
+					});
+				}
+			}
+		}
+
+		hits.sort_by(|a, b| {
+			b.score
+				.cmp(&a.score)
+				.then_with(|| a.item.title().cmp(b.item.title()))
+		});
+
+		hits
+	}
+}
+
+/// Scores a set of weighted fields against the query, or None if a word of the query wasn't found.
+/// Whole words score their field's weight, prefixes half of it, and a field containing the entire
+/// query as a phrase scores double.
+fn score(terms: &[String], phrase: &str, fields: &[(u32, String)]) -> Option<u32> {
+	let fields: Vec<(u32, Vec<String>)> = fields
+		.iter()
+		.map(|(weight, text)| (*weight, words(text)))
+		.collect();
+
+	let mut total = 0;
+
+	for term in terms {
+		let mut term_score = 0;
+
+		for (weight, words) in &fields {
+			if words.iter().any(|word| word == term) {
+				term_score += weight * 2;
+			} else if words.iter().any(|word| word.starts_with(term.as_str())) {
+				term_score += weight;
+			}
+		}
+
+		if term_score == 0 {
+			return None;
+		}
+		total += term_score;
+	}
+
+	if terms.len() > 1 {
+		for (weight, words) in &fields {
+			if words.join(" ").contains(phrase) {
+				total += weight * 2;
+			}
+		}
+	}
+
+	Some(total)
+}
+
+/// Splits text into lowercase words, ignoring punctuation.
+fn words(text: &str) -> Vec<String> {
+	text.split(|c: char| !c.is_alphanumeric())
+		.filter(|word| !word.is_empty())
+		.map(str::to_lowercase)
+		.collect()
+}
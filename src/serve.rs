@@ -0,0 +1,362 @@
+/*!
+A small HTTP server that serves a saved [Catalog](../catalog/struct.Catalog.html) with the same json as the
+`/api/v1` endpoints of the RoosterTeeth api, enabled by the `serve` feature.
+
+Only the catalog is served, so channels, shows, seasons and episodes are available but videos,
+users and search are not.
+
+| Endpoint | Query |
+| --- | --- |
+| `/api/v1/channels` | |
+| `/api/v1/shows` | `page`, `per_page`, `channel_id`, `order` |
+| `/api/v1/shows/{slug}` | |
+| `/api/v1/shows/{slug}/seasons` | `order` |
+| `/api/v1/seasons/{slug}/episodes` | `page`, `per_page`, `order` |
+| `/api/v1/episodes` | `page`, `per_page`, `channel_id`, `order` |
+| `/api/v1/watch/{slug}` | |
+
+Lists are in the order they were synced, which is oldest first, and reversed with `order=desc`.
+The latest episodes are ordered by their original air date instead.
+
+```no_run
+use roosterteeth_rs::catalog::Catalog;
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::serve::Server;
+
+let catalog = Catalog::load("catalog.json").unwrap();
+let server = Server::bind("127.0.0.1:0", catalog).unwrap().spawn();
+
+// The client works the same against the mirror, which is handy when testing code built on it.
+let requests = Requests::new(Credential::Anonymous).with_api_url(server.api_url());
+println!("{} channels", requests.list_channels().len());
+```
+*/
+
+use reqwest::Url;
+use serde::Serialize;
+use std::error;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use crate::catalog::Catalog;
+use crate::structs::ids::{ChannelSlug, EpisodeSlug, SeasonSlug, ShowSlug};
+use crate::structs::*;
+
+/// The prefix of every endpoint.
+pub const API_PATH: &str = "/api/v1";
+
+/// The page size used when a request doesn't ask for one.
+const DEFAULT_PER_PAGE: usize = 20;
+
+/// The status and json body of a response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+	pub status: u16,
+	pub body: String,
+}
+
+impl Response {
+	fn json<T: Serialize>(value: &T) -> Response {
+		Response {
+			status: 200,
+			body: serde_json::to_string(value).unwrap(),
+		}
+	}
+
+	fn error(status: u16, message: &str) -> Response {
+		Response {
+			status,
+			body: serde_json::json!({ "error": message }).to_string(),
+		}
+	}
+}
+
+/// Answers a GET request for a path such as `/api/v1/shows?page=2`, without going through HTTP.
+pub fn respond(catalog: &Catalog, path: &str) -> Response {
+	let url = match Url::parse("http://localhost").and_then(|base| base.join(path)) {
+		Ok(url) => url,
+		Err(_) => return Response::error(400, "Bad Request"),
+	};
+	let query = Query::new(&url);
+
+	let segments: Vec<&str> = match url.path().strip_prefix(API_PATH) {
+		Some(rest) => rest.split('/').filter(|s| !s.is_empty()).collect(),
+		None => return Response::error(404, "Not Found"),
+	};
+
+	match segments.as_slice() {
+		["channels"] => {
+			// Every channel fits on the first page
+			let channels: Vec<&channels::Channel> = catalog.channels.iter().collect();
+			let page = query.page(channels, catalog.channels.len().max(1));
+
+			Response::json(&channels::Root {
+				data: page.data,
+				page: page.page as u16,
+				per_page: page.per_page as u16,
+				total_pages: page.total_pages as u16,
+				total_results: page.total_results as u32,
+			})
+		}
+		["shows"] => {
+			let channel = query.channel();
+			let shows: Vec<&series::Series> = catalog
+				.series
+				.iter()
+				.filter(|series| {
+					channel
+						.as_ref()
+						.is_none_or(|c| &series.attributes.channel_slug == c)
+				})
+				.collect();
+			let page = query.page(query.ordered(shows), DEFAULT_PER_PAGE);
+
+			Response::json(&series::Root {
+				data: page.data,
+				page: Some(page.page as u16),
+				per_page: Some(page.per_page as u16),
+				total_pages: Some(page.total_pages as u16),
+				total_results: Some(page.total_results as u32),
+			})
+		}
+		["shows", slug] => match slug
+			.parse::<ShowSlug>()
+			.ok()
+			.and_then(|s| catalog.find_series(&s))
+		{
+			Some(series) => Response::json(&series::Root {
+				data: vec![series.clone()],
+				page: None,
+				per_page: None,
+				total_pages: None,
+				total_results: None,
+			}),
+			None => Response::error(404, "Show not found"),
+		},
+		["shows", slug, "seasons"] => match slug
+			.parse::<ShowSlug>()
+			.ok()
+			.filter(|s| catalog.find_series(s).is_some())
+		{
+			Some(slug) => Response::json(&seasons::Root {
+				data: query
+					.ordered(catalog.seasons_of(&slug).collect())
+					.into_iter()
+					.cloned()
+					.collect(),
+			}),
+			None => Response::error(404, "Show not found"),
+		},
+		["seasons", slug, "episodes"] => {
+			let slug = match slug.parse::<SeasonSlug>() {
+				Ok(slug) => slug,
+				Err(_) => return Response::error(404, "Season not found"),
+			};
+			if !catalog
+				.seasons
+				.iter()
+				.any(|season| season.attributes.slug == slug)
+			{
+				return Response::error(404, "Season not found");
+			}
+
+			let episodes = query.ordered(catalog.episodes_of(&slug).collect());
+
+			episodes_response(query.page(episodes, DEFAULT_PER_PAGE))
+		}
+		["episodes"] => {
+			let channel = query.channel();
+			let mut episodes: Vec<&episodes::Episode> = catalog
+				.episodes
+				.iter()
+				.filter(|episode| {
+					channel
+						.as_ref()
+						.is_none_or(|c| &episode.attributes.channel_slug == c)
+				})
+				.collect();
+			episodes.sort_by_key(|episode| episode.attributes.original_air_date);
+
+			episodes_response(query.page(query.ordered(episodes), DEFAULT_PER_PAGE))
+		}
+		["watch", slug] => {
+			let episode = slug.parse::<EpisodeSlug>().ok().and_then(|slug| {
+				catalog
+					.episodes
+					.iter()
+					.find(|episode| episode.attributes.slug == slug)
+			});
+
+			match episode {
+				Some(episode) => Response::json(&episodes::Root {
+					data: vec![episode.clone()],
+					page: None,
+					per_page: None,
+					total_pages: None,
+					total_results: None,
+				}),
+				None => Response::error(404, "Episode not found"),
+			}
+		}
+		_ => Response::error(404, "Not Found"),
+	}
+}
+
+fn episodes_response(page: Page<episodes::Episode>) -> Response {
+	Response::json(&episodes::Root {
+		data: page.data,
+		page: Some(page.page as u16),
+		per_page: Some(page.per_page as u16),
+		total_pages: Some(page.total_pages as u16),
+		total_results: Some(page.total_results as u32),
+	})
+}
+
+struct Query {
+	page: usize,
+	per_page: Option<usize>,
+	channel: Option<String>,
+	descending: bool,
+}
+
+struct Page<T> {
+	data: Vec<T>,
+	page: usize,
+	per_page: usize,
+	total_pages: usize,
+	total_results: usize,
+}
+
+impl Query {
+	fn new(url: &Url) -> Query {
+		let mut query = Query {
+			page: 1,
+			per_page: None,
+			channel: None,
+			descending: false,
+		};
+
+		for (key, value) in url.query_pairs() {
+			match key.as_ref() {
+				"page" => query.page = value.parse().unwrap_or(1).max(1),
+				"per_page" => query.per_page = value.parse().ok().filter(|n| *n > 0),
+				"channel_id" => query.channel = Some(value.into_owned()),
+				"order" => query.descending = value == "desc",
+				_ => {}
+			}
+		}
+
+		query
+	}
+
+	fn channel(&self) -> Option<ChannelSlug> {
+		self.channel.as_ref().and_then(|slug| slug.parse().ok())
+	}
+
+	fn ordered<T>(&self, mut items: Vec<T>) -> Vec<T> {
+		if self.descending {
+			items.reverse();
+		}
+
+		items
+	}
+
+	fn page<T: Clone>(&self, items: Vec<&T>, default_per_page: usize) -> Page<T> {
+		let per_page = self.per_page.unwrap_or(default_per_page);
+		let total_results = items.len();
+
+		Page {
+			data: items
+				.into_iter()
+				.skip((self.page - 1).saturating_mul(per_page))
+				.take(per_page)
+				.cloned()
+				.collect(),
+			page: self.page,
+			per_page,
+			total_pages: total_results.div_ceil(per_page),
+			total_results,
+		}
+	}
+}
+
+/// Serves a catalog over HTTP.
+pub struct Server {
+	http: Arc<tiny_http::Server>,
+	catalog: Arc<Catalog>,
+}
+
+impl Server {
+	/// Listens on an address such as `127.0.0.1:8080`, port 0 picks any free port.
+	pub fn bind(
+		address: &str,
+		catalog: Catalog,
+	) -> Result<Server, Box<dyn error::Error + Send + Sync + 'static>> {
+		Ok(Server {
+			http: Arc::new(tiny_http::Server::http(address)?),
+			catalog: Arc::new(catalog),
+		})
+	}
+
+	/// The url to pass to [with_api_url](../requests/struct.Requests.html#method.with_api_url).
+	pub fn api_url(&self) -> String {
+		format!("http://{}{}", self.http.server_addr(), API_PATH)
+	}
+
+	/// Answers requests until the process exits.
+	pub fn run(&self) {
+		serve(&self.http, &self.catalog);
+	}
+
+	/// Answers requests on a background thread until the returned handle is dropped.
+	pub fn spawn(self) -> Running {
+		let api_url = self.api_url();
+		let http = Arc::clone(&self.http);
+		let thread = thread::spawn(move || serve(&self.http, &self.catalog));
+
+		Running {
+			api_url,
+			http,
+			thread: Some(thread),
+		}
+	}
+}
+
+/// A server running on a background thread, which stops when dropped.
+pub struct Running {
+	api_url: String,
+	http: Arc<tiny_http::Server>,
+	thread: Option<JoinHandle<()>>,
+}
+
+impl Running {
+	pub fn api_url(&self) -> &str {
+		&self.api_url
+	}
+}
+
+impl Drop for Running {
+	fn drop(&mut self) {
+		self.http.unblock();
+
+		if let Some(thread) = self.thread.take() {
+			let _ = thread.join();
+		}
+	}
+}
+
+fn serve(http: &tiny_http::Server, catalog: &Catalog) {
+	for request in http.incoming_requests() {
+		let response = match request.method() {
+			tiny_http::Method::Get => respond(catalog, request.url()),
+			_ => Response::error(405, "Method Not Allowed"),
+		};
+
+		let header = tiny_http::Header::from_bytes("Content-Type", "application/json").unwrap();
+		let _ = request.respond(
+			tiny_http::Response::from_string(response.body)
+				.with_status_code(response.status)
+				.with_header(header),
+		);
+	}
+}
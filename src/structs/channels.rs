@@ -1,8 +1,9 @@
 use crate::structs::common::*;
-use serde::Deserialize;
+use crate::structs::ids::*;
+use serde::{Deserialize, Serialize};
 use std::string::String;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Root {
 	pub data: Vec<Channel>,
 	pub page: u16,
@@ -16,33 +17,33 @@ The Channel struct is a strongly typed wrapper of the channels endpoint.
 The full json format can be explored in your browser [here](https://svod-be.roosterteeth.com/api/v1/channels),
 or explore the object's fields below.
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Channel {
 	#[serde(rename = "type")]
 	pub kind: String,
 
-	pub id: u16,
-	pub uuid: String,
+	pub id: ChannelId,
+	pub uuid: ChannelUuid,
 
 	pub attributes: Attributes,
 	pub included: Included,
 	pub links: Links,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Attributes {
 	pub name: String,
 	pub importance: u16,
-	pub slug: String,
+	pub slug: ChannelSlug,
 	pub brand_color: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Included {
 	pub images: Vec<Image>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Links {
 	#[serde(rename = "self")]
 	pub reference: String,
@@ -1,9 +1,15 @@
-use serde::Deserialize;
+use crate::structs::ids::ImageId;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-#[derive(Debug, Deserialize)]
+/**
+Images are included with channels, series, seasons and episodes.
+Use [best_image](./fn.best_image.html) to pick one of them by type and orientation.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Image {
-	pub id: u32,
-	pub uuid: String,
+	pub id: ImageId,
+	pub uuid: Uuid,
 	#[serde(rename = "type")]
 	pub kind: String,
 	pub attributes: ImageAttributes,
@@ -12,12 +18,106 @@ pub struct Image {
 	//	pub included: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ImageAttributes {
 	pub thumb: String,
 	pub small: String,
 	pub medium: String,
 	pub large: String,
+
+	/**
+	```text
+	"orientation": "landscape"
+	```
+	*/
 	pub orientation: String,
+
+	/**
+	Known values are "poster", "cover", "thumbnail", "profile", "logo" and "title_card".
+
+	```text
+	"image_type": "thumbnail"
+	```
+	*/
 	pub image_type: String,
 }
+
+/// The sizes each image is available in, from smallest to largest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Size {
+	Thumb,
+	Small,
+	Medium,
+	/// The original upload, which doesn't have a fixed width.
+	Large,
+}
+
+impl Size {
+	pub const ALL: [Size; 4] = [Size::Thumb, Size::Small, Size::Medium, Size::Large];
+
+	/// The approximate width of a landscape image at this size, in pixels.
+	pub fn width(self) -> Option<u32> {
+		match self {
+			Size::Thumb => Some(320),
+			Size::Small => Some(480),
+			Size::Medium => Some(1280),
+			Size::Large => None,
+		}
+	}
+
+	/// The smallest size that is at least `width` pixels wide.
+	pub fn for_width(width: u32) -> Size {
+		Size::ALL
+			.iter()
+			.copied()
+			.find(|size| size.width().is_none_or(|w| w >= width))
+			.unwrap_or(Size::Large)
+	}
+}
+
+impl ImageAttributes {
+	pub fn url(&self, size: Size) -> &str {
+		match size {
+			Size::Thumb => &self.thumb,
+			Size::Small => &self.small,
+			Size::Medium => &self.medium,
+			Size::Large => &self.large,
+		}
+	}
+
+	/// The url of the smallest variant that is at least `width` pixels wide.
+	pub fn url_for_width(&self, width: u32) -> &str {
+		self.url(Size::for_width(width))
+	}
+}
+
+/**
+Picks the image that best matches an image type such as "poster", and optionally an orientation.
+
+An image matching both is preferred, followed by one matching the type and then one matching the orientation.
+Returns None if no image matches either.
+*/
+pub fn best_image<'a>(
+	images: &'a [Image],
+	image_type: &str,
+	orientation: Option<&str>,
+) -> Option<&'a Image> {
+	let score = |image: &Image| {
+		let type_matches = image.attributes.image_type == image_type;
+		let orientation_matches = orientation == Some(image.attributes.orientation.as_str());
+
+		match (type_matches, orientation_matches) {
+			(true, true) => 3,
+			(true, false) => 2,
+			(false, true) => 1,
+			(false, false) => 0,
+		}
+	};
+
+	images
+		.iter()
+		.filter(|image| score(image) > 0)
+		// max_by_key returns the last maximum, reverse so the first image listed wins ties.
+		.rev()
+		.max_by_key(|image| score(image))
+}
@@ -1,10 +1,12 @@
 use crate::structs::common::*;
+use crate::structs::ids::*;
 use chrono::offset::FixedOffset;
 use chrono::DateTime;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::string::String;
+use uuid::Uuid;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Root {
 	pub data: Vec<Episode>,
 	pub page: Option<u16>,
@@ -20,7 +22,7 @@ or explore the object's fields below.
 
 Most fields should have an example provided below, shown in the raw JSON format.
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Episode {
 	/**
 	Kind is a renamed reference to the type of object the metadata is describing, in this context it will always be episode.
@@ -47,7 +49,7 @@ pub struct Episode {
 	"id": 23242
 	```
 	*/
-	pub id: u32,
+	pub id: EpisodeId,
 
 	/**
 	Index appears to be the timestamp the metadata was generated at.
@@ -66,7 +68,7 @@ pub struct Episode {
 	"uuid": "ffac28dc-464d-11e7-a302-065410f210c4"
 	```
 	*/
-	pub uuid: String,
+	pub uuid: EpisodeUuid,
 	pub attributes: Attributes,
 	pub links: Links,
 	pub canonical_links: CanonicalLinks,
@@ -74,7 +76,7 @@ pub struct Episode {
 }
 
 /// Most elements here will be fairly self-documenting.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Attributes {
 	/**
 	```text
@@ -90,7 +92,7 @@ pub struct Attributes {
 	"slug": "red-vs-blue-season-1-episode-1"
 	```
 	*/
-	pub slug: String,
+	pub slug: EpisodeSlug,
 
 	/**
 	```text
@@ -156,28 +158,28 @@ pub struct Attributes {
 	"channel_id": "92b6bb21-91d2-4b1b-bf95-3268fa0d9939"
 	``
 	*/
-	pub channel_id: String,
+	pub channel_id: ChannelUuid,
 
 	/**
 	```text
 	"channel_slug": "rooster-teeth"
 	```
 	*/
-	pub channel_slug: String,
+	pub channel_slug: ChannelSlug,
 
 	/**
 	```text
 	"season_id": "ffa11de8-464d-11e7-a302-065410f210c4"
 	```
 	*/
-	pub season_id: String,
+	pub season_id: SeasonUuid,
 
 	/**
 	```text
 	"season_slug": "red-vs-blue-season-1"
 	```
 	*/
-	pub season_slug: String,
+	pub season_slug: SeasonSlug,
 
 	/**
 	```text
@@ -198,14 +200,14 @@ pub struct Attributes {
 	"show_id": "ff925ff9-464d-11e7-a302-065410f210c4"
 	```
 	*/
-	pub show_id: String,
+	pub show_id: ShowUuid,
 
 	/**
 	```text
 	"show_slug": "red-vs-blue"
 	```
 	*/
-	pub show_slug: String,
+	pub show_slug: ShowSlug,
 
 	/**
 	```text
@@ -296,7 +298,7 @@ Links is a reference to the various other API endpoints relevant to this entry.
 
 TODO: Implement these as object-oriented functions!
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Links {
 	#[serde(rename = "self")]
 	pub reference: String,
@@ -312,7 +314,7 @@ pub struct Links {
 /**
 Canonical Links references the public webpage this data describes, rather than the API endpoint
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CanonicalLinks {
 	/**
 	```text
@@ -330,17 +332,17 @@ pub struct CanonicalLinks {
 	pub show: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Included {
 	pub images: Vec<Image>,
 	pub tags: Vec<Tag>,
 	pub cast_members: Vec<CastMember>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Tag {
-	pub id: String,
-	pub uuid: String,
+	pub id: TagId,
+	pub uuid: Uuid,
 	#[serde(rename = "type")]
 	pub kind: String,
 
@@ -349,16 +351,16 @@ pub struct Tag {
 	//	pub included: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TagAttribute {
 	pub tag: String,
-	pub slug: String,
+	pub slug: TagSlug,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CastMember {
-	pub id: String,
-	pub uuid: String,
+	pub id: CastMemberId,
+	pub uuid: Uuid,
 	#[serde(rename = "type")]
 	pub kind: String,
 	pub attributes: CastMemberAttributes,
@@ -366,7 +368,7 @@ pub struct CastMember {
 	//	pub included: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CastMemberAttributes {
 	pub name: String,
 }
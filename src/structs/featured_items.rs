@@ -0,0 +1,54 @@
+use crate::structs::common::*;
+use crate::structs::ids::*;
+use chrono::offset::FixedOffset;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::string::String;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Root {
+	pub data: Vec<FeaturedItem>,
+}
+
+/**
+Featured items are the promotions shown at the top of a channel's page.
+The full json format can be explored in your browser [here](https://svod-be.roosterteeth.com/api/v1/channels/rooster-teeth/featured_items),
+or explore the object's fields below.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeaturedItem {
+	#[serde(rename = "type")]
+	pub kind: String,
+	pub id: FeaturedItemId,
+	pub uuid: Uuid,
+	pub attributes: Attributes,
+	pub included: Included,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attributes {
+	pub title: String,
+	pub subtitle: Option<String>,
+	pub description: Option<String>,
+
+	/**
+	Where the promotion links to, usually a path on the website.
+
+	```text
+	"link": "/series/red-vs-blue"
+	```
+	*/
+	pub link: String,
+
+	/// The position of the item on the page, starting at 0.
+	pub position: u16,
+
+	pub starts_at: Option<DateTime<FixedOffset>>,
+	pub ends_at: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Included {
+	pub images: Vec<Image>,
+}
@@ -0,0 +1,354 @@
+/*!
+Strongly typed identifiers, so that the id, uuid or slug of one kind of resource can't be used for another.
+
+Every resource is addressed in three ways: a numeric id, a uuid and a slug used in urls.
+Slugs and uuids can be parsed from strings, and compare equal to their string representation.
+
+```
+use roosterteeth_rs::structs::ids::{SeasonSlug, ShowSlug};
+
+let show: ShowSlug = "red-vs-blue".parse().unwrap();
+let season: SeasonSlug = "red-vs-blue-season-1".parse().unwrap();
+
+assert_eq!(show, "red-vs-blue");
+assert!("red vs blue".parse::<ShowSlug>().is_err());
+```
+
+Mixing up slugs is caught at compile time:
+
+```compile_fail
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::structs::ids::SeasonSlug;
+
+let requests = Requests::new(Credential::Anonymous);
+let season: SeasonSlug = "red-vs-blue-season-1".parse().unwrap();
+
+requests.get_series(&season);
+```
+*/
+
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Returned when a string isn't a valid identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidId {
+	kind: &'static str,
+	value: String,
+}
+
+impl fmt::Display for InvalidId {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "\"{}\" is not a valid {}", self.value, self.kind)
+	}
+}
+
+impl error::Error for InvalidId {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		None
+	}
+}
+
+// Slugs appear in the path of API urls, so anything that would change the meaning of the url is rejected.
+fn is_valid_slug(slug: &str) -> bool {
+	!slug.is_empty()
+		&& !slug
+			.chars()
+			.any(|c| c.is_whitespace() || c.is_control() || "/?#%&\\".contains(c))
+}
+
+macro_rules! numeric_id {
+	($(#[$meta:meta])* $name:ident($inner:ty)) => {
+		$(#[$meta])*
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+		#[serde(transparent)]
+		pub struct $name(pub $inner);
+
+		impl fmt::Display for $name {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				self.0.fmt(f)
+			}
+		}
+
+		impl FromStr for $name {
+			type Err = InvalidId;
+
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				s.parse().map($name).map_err(|_| InvalidId {
+					kind: stringify!($name),
+					value: s.to_string(),
+				})
+			}
+		}
+
+		impl PartialEq<$inner> for $name {
+			fn eq(&self, other: &$inner) -> bool {
+				self.0 == *other
+			}
+		}
+	};
+}
+
+/// Like numeric_id, but (de)serialized as a string because that is how the API returns it.
+macro_rules! string_numeric_id {
+	($(#[$meta:meta])* $name:ident($inner:ty)) => {
+		$(#[$meta])*
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+		#[serde(try_from = "String", into = "String")]
+		pub struct $name(pub $inner);
+
+		impl fmt::Display for $name {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				self.0.fmt(f)
+			}
+		}
+
+		impl FromStr for $name {
+			type Err = InvalidId;
+
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				s.parse().map($name).map_err(|_| InvalidId {
+					kind: stringify!($name),
+					value: s.to_string(),
+				})
+			}
+		}
+
+		impl TryFrom<String> for $name {
+			type Error = InvalidId;
+
+			fn try_from(s: String) -> Result<Self, Self::Error> {
+				s.parse()
+			}
+		}
+
+		impl From<$name> for String {
+			fn from(id: $name) -> String {
+				id.to_string()
+			}
+		}
+
+		impl PartialEq<$inner> for $name {
+			fn eq(&self, other: &$inner) -> bool {
+				self.0 == *other
+			}
+		}
+	};
+}
+
+macro_rules! uuid_id {
+	($(#[$meta:meta])* $name:ident) => {
+		$(#[$meta])*
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+		#[serde(transparent)]
+		pub struct $name(pub uuid::Uuid);
+
+		impl fmt::Display for $name {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				// Hyphenated and lowercase, the same as the API.
+				self.0.hyphenated().fmt(f)
+			}
+		}
+
+		impl FromStr for $name {
+			type Err = InvalidId;
+
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				uuid::Uuid::parse_str(s).map($name).map_err(|_| InvalidId {
+					kind: stringify!($name),
+					value: s.to_string(),
+				})
+			}
+		}
+
+		impl PartialEq<str> for $name {
+			fn eq(&self, other: &str) -> bool {
+				uuid::Uuid::parse_str(other).is_ok_and(|other| self.0 == other)
+			}
+		}
+
+		impl PartialEq<&str> for $name {
+			fn eq(&self, other: &&str) -> bool {
+				*self == **other
+			}
+		}
+	};
+}
+
+macro_rules! slug {
+	($(#[$meta:meta])* $name:ident) => {
+		$(#[$meta])*
+		#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+		#[serde(try_from = "String", into = "String")]
+		pub struct $name(String);
+
+		impl $name {
+			pub fn as_str(&self) -> &str {
+				&self.0
+			}
+		}
+
+		impl fmt::Display for $name {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.write_str(&self.0)
+			}
+		}
+
+		impl FromStr for $name {
+			type Err = InvalidId;
+
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				if is_valid_slug(s) {
+					Ok($name(s.to_string()))
+				} else {
+					Err(InvalidId {
+						kind: stringify!($name),
+						value: s.to_string(),
+					})
+				}
+			}
+		}
+
+		impl TryFrom<String> for $name {
+			type Error = InvalidId;
+
+			fn try_from(s: String) -> Result<Self, Self::Error> {
+				s.parse()
+			}
+		}
+
+		impl From<$name> for String {
+			fn from(slug: $name) -> String {
+				slug.0
+			}
+		}
+
+		impl AsRef<str> for $name {
+			fn as_ref(&self) -> &str {
+				&self.0
+			}
+		}
+
+		impl PartialEq<str> for $name {
+			fn eq(&self, other: &str) -> bool {
+				self.0 == other
+			}
+		}
+
+		impl PartialEq<&str> for $name {
+			fn eq(&self, other: &&str) -> bool {
+				self.0 == *other
+			}
+		}
+	};
+}
+
+numeric_id!(
+	/// The numeric id of a channel.
+	ChannelId(u16)
+);
+numeric_id!(
+	/// The numeric id of a show.
+	ShowId(u32)
+);
+numeric_id!(
+	/// The numeric id of a season.
+	SeasonId(u32)
+);
+numeric_id!(
+	/// The numeric id of an episode.
+	EpisodeId(u32)
+);
+numeric_id!(
+	/// The numeric id of a video.
+	VideoId(u32)
+);
+numeric_id!(
+	/// The numeric id of a livestream.
+	LivestreamId(u32)
+);
+numeric_id!(
+	/// The numeric id of a product.
+	ProductId(u32)
+);
+numeric_id!(
+	/// The numeric id of a collection of products.
+	ProductCollectionId(u32)
+);
+numeric_id!(
+	/// The numeric id of a featured item.
+	FeaturedItemId(u32)
+);
+numeric_id!(
+	/// The numeric id of an image.
+	ImageId(u32)
+);
+numeric_id!(
+	/// The numeric id of a user account.
+	UserId(u32)
+);
+string_numeric_id!(
+	/// The numeric id of a tag, returned as a string by the API.
+	TagId(u32)
+);
+string_numeric_id!(
+	/// The numeric id of a cast member, returned as a string by the API.
+	CastMemberId(u32)
+);
+
+uuid_id!(
+	/// The uuid of a channel, also referenced by the `channel_id` of shows and episodes.
+	ChannelUuid
+);
+uuid_id!(
+	/// The uuid of a show, also referenced by the `show_id` of seasons and episodes.
+	ShowUuid
+);
+uuid_id!(
+	/// The uuid of a season, also referenced by the `season_id` of episodes.
+	SeasonUuid
+);
+uuid_id!(
+	/// The uuid of an episode.
+	EpisodeUuid
+);
+uuid_id!(
+	/// The uuid of a video.
+	VideoUuid
+);
+uuid_id!(
+	/// The uuid of a livestream.
+	LivestreamUuid
+);
+uuid_id!(
+	/// The uuid of a user account, used in the urls of the user's watchlist and watch progress.
+	UserUuid
+);
+
+slug!(
+	/// The slug of a channel, for example "rooster-teeth".
+	ChannelSlug
+);
+slug!(
+	/// The slug of a show, for example "red-vs-blue".
+	ShowSlug
+);
+slug!(
+	/// The slug of a season, for example "red-vs-blue-season-1".
+	SeasonSlug
+);
+slug!(
+	/// The slug of an episode, for example "red-vs-blue-season-1-episode-1".
+	EpisodeSlug
+);
+slug!(
+	/// The slug of a livestream, for example "rt-podcast-live".
+	LivestreamSlug
+);
+slug!(
+	/// The slug of a tag, for example "halo".
+	TagSlug
+);
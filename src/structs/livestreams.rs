@@ -0,0 +1,161 @@
+use crate::structs::common::*;
+use crate::structs::ids::*;
+use chrono::offset::FixedOffset;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::string::String;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Root {
+	pub data: Vec<Livestream>,
+}
+
+/**
+The Livestream struct is a strongly typed wrapper of a channel's livestreams endpoint.
+The full json format can be explored in your browser [here](https://svod-be.roosterteeth.com/api/v1/channels/rooster-teeth/livestreams),
+or explore the object's fields below.
+
+Streams are listed ahead of time, use [is_live](#method.is_live) to check whether one is currently broadcasting.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Livestream {
+	#[serde(rename = "type")]
+	pub kind: String,
+	pub id: LivestreamId,
+	pub uuid: LivestreamUuid,
+	pub attributes: Attributes,
+	pub links: Links,
+	pub included: Included,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attributes {
+	pub title: String,
+	pub slug: LivestreamSlug,
+	pub description: Option<String>,
+
+	pub channel_id: ChannelUuid,
+	pub channel_slug: ChannelSlug,
+
+	/**
+	The HLS master playlist of the stream, only present while it is broadcasting.
+
+	```text
+	"url": "https://live.roosterteeth.com/rt-podcast-live/index.m3u8"
+	```
+	*/
+	pub url: Option<String>,
+
+	/**
+	```text
+	"is_live": true
+	```
+	*/
+	pub is_live: bool,
+	pub is_sponsors_only: bool,
+
+	/// When the stream is scheduled to start and end, these are null for always-on channels.
+	pub starts_at: Option<DateTime<FixedOffset>>,
+	pub ends_at: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Links {
+	#[serde(rename = "self")]
+	pub reference: String,
+	pub channel: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Included {
+	pub images: Vec<Image>,
+}
+
+impl Livestream {
+	pub fn is_live(&self) -> bool {
+		self.attributes.is_live
+	}
+
+	/// The HLS master playlist of the stream, or None if it isn't broadcasting.
+	/// The playlist can be parsed with the [hls](../../hls/index.html) module.
+	pub fn hls_url(&self) -> Option<&str> {
+		if self.is_live() {
+			self.attributes.url.as_deref()
+		} else {
+			None
+		}
+	}
+}
+
+/// A change in the state of a stream, as reported by a [Monitor](./struct.Monitor.html).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+	Started(Box<Livestream>),
+	Ended(LivestreamSlug),
+}
+
+/**
+Detects streams going live or ending by comparing successive polls of `list_livestreams()`.
+
+```no_run
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::structs::livestreams::{Event, Monitor};
+use std::{thread, time::Duration};
+
+let requests = Requests::new(Credential::Anonymous);
+let channel = "rooster-teeth".parse().unwrap();
+let mut monitor = Monitor::new();
+
+loop {
+	for event in monitor.update(&requests.list_livestreams(&channel)) {
+		match event {
+			Event::Started(stream) => println!("{} is live at {:?}", stream.attributes.title, stream.hls_url()),
+			Event::Ended(slug) => println!("{} has ended", slug),
+		}
+	}
+
+	thread::sleep(Duration::from_secs(60));
+}
+```
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Monitor {
+	live: HashSet<LivestreamSlug>,
+}
+
+impl Monitor {
+	pub fn new() -> Self {
+		Monitor::default()
+	}
+
+	/// Records the latest list of streams, returning the ones that started or ended since the last update.
+	/// A stream missing from the list is treated as having ended.
+	pub fn update(&mut self, streams: &[Livestream]) -> Vec<Event> {
+		let mut events = Vec::new();
+		let mut live = HashSet::new();
+
+		for stream in streams.iter().filter(|stream| stream.is_live()) {
+			let slug = &stream.attributes.slug;
+
+			if !self.live.contains(slug) {
+				events.push(Event::Started(Box::new(stream.clone())));
+			}
+			live.insert(slug.clone());
+		}
+
+		let mut ended: Vec<&LivestreamSlug> = self.live.difference(&live).collect();
+		// HashSet iteration order is random, keep the events deterministic.
+		ended.sort();
+		events.extend(ended.into_iter().cloned().map(Event::Ended));
+
+		self.live = live;
+
+		events
+	}
+
+	/// The slugs of the streams that were live as of the last update.
+	pub fn live(&self) -> impl Iterator<Item = &LivestreamSlug> {
+		self.live.iter()
+	}
+}
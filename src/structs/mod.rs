@@ -1,6 +1,11 @@
-mod common;
 pub mod channels;
+pub mod common;
 pub mod episodes;
+pub mod featured_items;
+pub mod ids;
+pub mod livestreams;
+pub mod products;
 pub mod seasons;
 pub mod series;
+pub mod users;
 pub mod videos;
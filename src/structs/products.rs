@@ -0,0 +1,87 @@
+use crate::structs::common::*;
+use crate::structs::ids::*;
+use serde::{Deserialize, Serialize};
+use std::string::String;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionRoot {
+	pub data: Vec<ProductCollection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductRoot {
+	pub data: Vec<Product>,
+}
+
+/**
+A ProductCollection is a group of merchandise linked to a channel or show.
+The full json format can be explored in your browser [here](https://svod-be.roosterteeth.com/api/v1/shows/red-vs-blue/product_collections),
+or explore the object's fields below.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductCollection {
+	#[serde(rename = "type")]
+	pub kind: String,
+	pub id: ProductCollectionId,
+	pub uuid: Uuid,
+	pub attributes: CollectionAttributes,
+	pub included: CollectionIncluded,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionAttributes {
+	pub title: String,
+	pub slug: String,
+	pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionIncluded {
+	pub products: Vec<Product>,
+}
+
+/**
+A single item of merchandise in the Rooster Teeth store.
+The full json format can be explored in your browser [here](https://svod-be.roosterteeth.com/api/v1/watch/red-vs-blue-season-1-episode-1/products),
+or explore the object's fields below.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Product {
+	#[serde(rename = "type")]
+	pub kind: String,
+	pub id: ProductId,
+	pub uuid: Uuid,
+	pub attributes: ProductAttributes,
+	pub included: ProductIncluded,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductAttributes {
+	pub title: String,
+	pub description: Option<String>,
+
+	/**
+	The product page in the store.
+
+	```text
+	"url": "https://store.roosterteeth.com/products/red-vs-blue-grif-shirt"
+	```
+	*/
+	pub url: String,
+
+	/**
+	The price as displayed in the store, including the currency symbol.
+
+	```text
+	"price": "$25.00"
+	```
+	*/
+	pub price: Option<String>,
+	pub currency: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductIncluded {
+	pub images: Vec<Image>,
+}
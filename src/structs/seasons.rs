@@ -1,10 +1,11 @@
 use crate::structs::common::*;
+use crate::structs::ids::*;
 use chrono::offset::FixedOffset;
 use chrono::DateTime;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::string::String;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Root {
 	pub data: Vec<Season>,
 }
@@ -14,47 +15,47 @@ The Season struct is a strongly typed wrapper of a show's seasons endpoint.
 The full json format can be explored in your browser [here](https://svod-be.roosterteeth.com/api/v1/shows/red-vs-blue/seasons?order=desc),
 or explore the object's fields below.
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Season {
 	#[serde(rename = "_index")]
 	pub index: String,
 	pub sort: Vec<u16>,
-	pub id: u32,
+	pub id: SeasonId,
 	#[serde(rename = "type")]
 	pub kind: String,
-	pub uuid: String,
+	pub uuid: SeasonUuid,
 	pub attributes: Attributes,
 	pub links: Links,
 	pub included: Included,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Attributes {
 	pub title: String,
 	pub description: String,
-	pub slug: String,
+	pub slug: SeasonSlug,
 	pub number: u16,
-	pub show_id: String,
-	pub show_slug: String,
+	pub show_id: ShowUuid,
+	pub show_slug: ShowSlug,
 	pub episodes_available: EpisodesAvailable,
 	pub published_at: DateTime<FixedOffset>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EpisodesAvailable {
 	pub sponsor: bool,
 	pub member: bool,
 	pub public: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Links {
 	#[serde(rename = "self")]
 	pub reference: String,
 	pub episodes: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Included {
 	pub images: Vec<Image>,
 }
@@ -1,10 +1,13 @@
 use crate::structs::common::*;
+use crate::structs::ids::*;
+use crate::structs::{episodes, videos};
 use chrono::offset::FixedOffset;
 use chrono::DateTime;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 use std::string::String;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Root {
 	pub data: Vec<Series>,
 
@@ -20,25 +23,25 @@ The Series struct is a strongly typed wrapper of the show endpoint.
 The full json format can be explored in your browser [here](https://svod-be.roosterteeth.com/api/v1/shows?per_page=1000),
 or explore the object's fields below.
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Series {
 	#[serde(rename = "_index")]
 	pub index: String,
 	pub sort: Vec<u64>,
-	pub id: u32,
+	pub id: ShowId,
 	#[serde(rename = "type")]
 	pub kind: String,
-	pub uuid: String,
+	pub uuid: ShowUuid,
 	pub attributes: Attributes,
 	pub links: Links,
 	pub canonical_links: CanonicalLinks,
 	pub included: Included,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Attributes {
 	pub title: String,
-	pub slug: String,
+	pub slug: ShowSlug,
 	pub genres: Vec<String>,
 	pub is_sponsors_only: bool,
 
@@ -48,8 +51,8 @@ pub struct Attributes {
 
 	pub summary: String,
 	pub category: String,
-	pub channel_id: String,
-	pub channel_slug: String,
+	pub channel_id: ChannelUuid,
+	pub channel_slug: ChannelSlug,
 	pub season_count: u16,
 	pub episode_count: u32,
 
@@ -59,7 +62,7 @@ pub struct Attributes {
 	pub blacklisted_countries: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Links {
 	#[serde(rename = "self")]
 	pub reference: String,
@@ -72,14 +75,59 @@ pub struct Links {
 	pub rich_card_reference_url: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CanonicalLinks {
 	#[serde(rename = "self")]
 	pub reference: String,
 	pub s1e1: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Included {
 	pub images: Vec<Image>,
 }
+
+/// The response of a show's `bonus_features` link.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BonusRoot {
+	pub data: Vec<BonusFeature>,
+	pub page: Option<u16>,
+	pub per_page: Option<u16>,
+	pub total_pages: Option<u16>,
+	pub total_results: Option<u32>,
+}
+
+/// Bonus features are usually episodes, which can be watched with get_video(), but can also be bare videos.
+/// They are told apart by their `type`, and anything unexpected is kept as `Other`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum BonusFeature {
+	Episode(Box<episodes::Episode>),
+	Video(Box<videos::Video>),
+	Other(Value),
+}
+
+impl BonusFeature {
+	pub fn into_episode(self) -> Option<episodes::Episode> {
+		match self {
+			BonusFeature::Episode(episode) => Some(*episode),
+			_ => None,
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for BonusFeature {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let value = Value::deserialize(deserializer)?;
+
+		let parsed = match value.get("type").and_then(Value::as_str) {
+			Some("episode") | Some("bonus_feature") => {
+				serde_json::from_value(value.clone()).map(BonusFeature::Episode)
+			}
+			Some("video") => serde_json::from_value(value.clone()).map(BonusFeature::Video),
+			_ => return Ok(BonusFeature::Other(value)),
+		};
+
+		Ok(parsed.unwrap_or(BonusFeature::Other(value)))
+	}
+}
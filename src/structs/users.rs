@@ -0,0 +1,92 @@
+use crate::schedule::Tier;
+use crate::structs::episodes::Episode;
+use crate::structs::ids::*;
+use chrono::offset::FixedOffset;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::string::String;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Root {
+	pub data: Vec<User>,
+}
+
+/**
+The User struct is the profile of the logged in user, as returned by `get_profile()`.
+Unlike the rest of the API, this requires a bearer token and can't be explored in the browser.
+*/
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+	#[serde(rename = "type")]
+	pub kind: String,
+	pub id: UserId,
+	pub uuid: UserUuid,
+	pub attributes: Attributes,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attributes {
+	pub username: String,
+	pub display_title: Option<String>,
+	pub about: Option<String>,
+	pub location: Option<String>,
+
+	/**
+	The name of the membership, and its numeric equivalent comparable to an episode's `member_tier_i`.
+
+	```text
+	"member_tier": "first",
+	"member_tier_i": 2
+	```
+	*/
+	pub member_tier: String,
+	pub member_tier_i: i8,
+
+	/**
+	```text
+	"is_sponsor": true
+	```
+	*/
+	pub is_sponsor: bool,
+	pub created_at: DateTime<FixedOffset>,
+}
+
+impl User {
+	/// The tier the user watches at, for use with the [schedule](../../schedule/index.html) module.
+	/// Any logged in user is at least a member.
+	pub fn tier(&self) -> Tier {
+		if self.attributes.is_sponsor {
+			Tier::Sponsor
+		} else {
+			Tier::Member
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressRoot {
+	pub data: Vec<WatchProgress>,
+}
+
+/// How far into an episode the user has watched, synced between devices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchProgress {
+	pub episode_id: EpisodeUuid,
+
+	/**
+	The position in seconds.
+
+	```text
+	"value": 120
+	```
+	*/
+	pub value: u32,
+	pub updated_at: DateTime<FixedOffset>,
+}
+
+impl WatchProgress {
+	/// Whether the position is within the last 30 seconds of the episode, which the website treats as watched.
+	pub fn is_finished(&self, episode: &Episode) -> bool {
+		self.value + 30 >= episode.attributes.length
+	}
+}
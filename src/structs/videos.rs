@@ -1,9 +1,10 @@
+use crate::structs::ids::*;
 use chrono::offset::FixedOffset;
 use chrono::DateTime;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::string::String;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Root {
 	pub data: Vec<Video>,
 }
@@ -13,27 +14,27 @@ The Video struct is a strongly typed wrapper of an episode's video endpoint.
 The full json format can be explored in your browser [here](https://svod-be.roosterteeth.com/api/v1/watch/red-vs-blue-season-2-episode-22/videos),
 or explore the object's fields below.
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Video {
 	#[serde(rename = "_index")]
 	pub index: String,
 	#[serde(rename = "_score")]
 	pub score: f32,
-	pub id: u32,
+	pub id: VideoId,
 	#[serde(rename = "type")]
 	pub kind: String,
-	pub uuid: String,
+	pub uuid: VideoUuid,
 	pub attributes: Attributes,
 	pub links: Links,
 	//	pub included: Included
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Attributes {
 	pub url: String,
-	pub content_id: u32,
-	pub content_slug: String,
-	pub content_uuid: String,
+	pub content_id: EpisodeId,
+	pub content_slug: EpisodeSlug,
+	pub content_uuid: EpisodeUuid,
 
 	pub public_golive_at: DateTime<FixedOffset>,
 	pub sponsor_golive_at: DateTime<FixedOffset>,
@@ -52,7 +53,7 @@ pub struct Attributes {
 	pub ad_config: Option<AdConfig>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AdConfig {
 	pub nw: String,
 	pub caid: String,
@@ -63,7 +64,7 @@ pub struct AdConfig {
 	pub midroll: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Links {
 	#[serde(rename = "self")]
 	pub reference: String,
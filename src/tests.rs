@@ -1,4 +1,74 @@
-use crate::requests::{Credential, Requests};
+use crate::access::{can_watch, denial, VideoUnavailable};
+use crate::archive::Archive;
+use crate::calendar;
+use crate::catalog::Catalog;
+use crate::diff::{Change, Diff};
+use crate::export::{self, Csv, JsonLines, Nested};
+use crate::feed::{Enclosure, Feed, Podcast};
+use crate::geo::{self, Country, Restricted};
+use crate::hls;
+use crate::layout::{self, Plan};
+use crate::nfo;
+use crate::pages::Pages;
+use crate::requests::{Credential, Requests, UserError};
+use crate::resource::{Resolved, Resource, UrlError};
+use crate::schedule::{Availability, Schedule, Tier};
+use crate::search::{self, Item, SearchResult};
+use crate::structs::common::{best_image, Size};
+use crate::structs::ids::{EpisodeId, ShowSlug, TagId, TagSlug};
+use crate::structs::livestreams::{Event, Monitor};
+use crate::structs::{
+	channels, episodes, featured_items, livestreams, products, seasons, series, users, videos,
+};
+use crate::transport::{Body, Cassette, Interaction, Recorder, Replayer, Transport};
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+fn fixture_episodes() -> Vec<episodes::Episode> {
+	let root: episodes::Root =
+		serde_json::from_str(include_str!("../fixtures/episodes.json")).unwrap();
+	root.data
+}
+
+fn fixture_catalog() -> Catalog {
+	let channels: channels::Root =
+		serde_json::from_str(include_str!("../fixtures/channels.json")).unwrap();
+	let series: series::Root =
+		serde_json::from_str(include_str!("../fixtures/series.json")).unwrap();
+	let seasons: seasons::Root =
+		serde_json::from_str(include_str!("../fixtures/seasons.json")).unwrap();
+
+	Catalog {
+		synced_at: utc("2020-03-10T00:00:00Z"),
+		channels: channels.data,
+		series: series.data,
+		seasons: seasons.data,
+		episodes: fixture_episodes(),
+	}
+}
+
+/// Parses the fixture, then checks that serializing it yields the same object and keeps the API's field names.
+fn round_trip<T>(json: &str) -> serde_json::Value
+where
+	T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+	let parsed: T = serde_json::from_str(json).unwrap();
+	let serialized = serde_json::to_value(&parsed).unwrap();
+
+	assert_eq!(
+		serde_json::from_value::<T>(serialized.clone()).unwrap(),
+		parsed
+	);
+
+	serialized
+}
+
+fn utc(timestamp: &str) -> DateTime<Utc> {
+	timestamp.parse().unwrap()
+}
 
 #[test]
 fn list_channels() {
@@ -35,7 +105,7 @@ fn list_series() {
 #[test]
 fn get_seasons() {
 	let requests = Requests::new(Credential::Anonymous);
-	let seasons = requests.get_seasons("red-vs-blue", Some("asc"));
+	let seasons = requests.get_seasons(&"red-vs-blue".parse().unwrap(), Some("asc"));
 
 	assert_eq!(seasons[0].attributes.number, 1);
 
@@ -45,7 +115,7 @@ fn get_seasons() {
 #[test]
 fn get_season_episodes() {
 	let requests = Requests::new(Credential::Anonymous);
-	let episodes = requests.get_season_episodes("red-vs-blue-season-1", None);
+	let episodes = requests.get_season_episodes(&"red-vs-blue-season-1".parse().unwrap(), None);
 
 	assert_eq!(&episodes[0].attributes.title, "Episode 1: Why Are We Here?");
 
@@ -58,7 +128,7 @@ fn get_season_episodes() {
 #[test]
 fn get_series() {
 	let requests = Requests::new(Credential::Anonymous);
-	let series = requests.get_series("red-vs-blue");
+	let series = requests.get_series(&"red-vs-blue".parse().unwrap());
 
 	assert_eq!(&series.uuid, "ff925ff9-464d-11e7-a302-065410f210c4");
 
@@ -68,7 +138,11 @@ fn get_series() {
 #[test]
 fn get_episode() {
 	let requests = Requests::new(Credential::Anonymous);
-	let episode = requests.get_episode("million-dollars-but-season-1-magic-dogs-and-muscle-men");
+	let episode = requests.get_episode(
+		&"million-dollars-but-season-1-magic-dogs-and-muscle-men"
+			.parse()
+			.unwrap(),
+	);
 
 	assert_eq!(&episode.uuid, "0006f0d4-464e-11e7-a302-065410f210c4");
 
@@ -79,7 +153,11 @@ fn get_episode() {
 fn get_video() {
 	let requests = Requests::new(Credential::Anonymous);
 	let video = requests
-		.get_video("million-dollars-but-season-1-magic-dogs-and-muscle-men")
+		.get_video(
+			&"million-dollars-but-season-1-magic-dogs-and-muscle-men"
+				.parse()
+				.unwrap(),
+		)
 		.unwrap();
 
 	assert_eq!(&video.uuid, "48bb3e93-04ea-4f90-a6ec-f9aff2c79dfa");
@@ -100,8 +178,1383 @@ fn process_new_videos() {
 	*/
 	let requests = Requests::new(Credential::Anonymous);
 
-	let seasons = requests.get_seasons("rt-animated-adventures", None);
+	let seasons = requests.get_seasons(&"rt-animated-adventures".parse().unwrap(), None);
 	let episodes = requests.get_season_episodes(&seasons[0].attributes.slug, None);
 
 	assert_eq!(&episodes[0].attributes.show_slug, "rt-animated-adventures");
 }
+
+#[test]
+fn episode_availability() {
+	let episodes = fixture_episodes();
+	let now = utc("2020-03-10T00:00:00Z");
+
+	assert_eq!(
+		episodes[0].availability_for(Tier::Sponsor, now),
+		Availability::Available
+	);
+	assert_eq!(
+		episodes[0].availability_for(Tier::Public, now),
+		Availability::Upcoming(episodes[0].attributes.public_golive_at)
+	);
+
+	// Sponsor only episodes never become available to the public.
+	assert_eq!(
+		episodes[1].availability_for(Tier::Member, now),
+		Availability::Unavailable
+	);
+}
+
+#[test]
+fn upcoming_schedule() {
+	let episodes = fixture_episodes();
+	let schedule = Schedule::upcoming(&episodes, utc("2020-03-10T00:00:00Z"));
+
+	let tiers: Vec<Tier> = schedule.events().iter().map(|event| event.tier).collect();
+	assert_eq!(tiers, vec![Tier::Public, Tier::Member, Tier::Sponsor]);
+
+	assert_eq!(schedule.for_tier(Tier::Sponsor).count(), 1);
+	assert_eq!(
+		&schedule.next().unwrap().episode.attributes.slug,
+		"red-vs-blue-season-1-episode-1"
+	);
+}
+
+#[test]
+fn calendar_export() {
+	let episodes = fixture_episodes();
+	let schedule = Schedule::upcoming(&episodes, utc("2020-03-10T00:00:00Z"));
+	let ics = calendar::to_ics("Red vs. Blue", &schedule, utc("2020-03-10T00:00:00Z"));
+
+	assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+	assert!(ics.ends_with("END:VCALENDAR\r\n"));
+	assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+	assert!(ics.contains("UID:ffac28dc-464d-11e7-a302-065410f210c4-public@roosterteeth.com\r\n"));
+	assert!(ics.contains("DTSTART:20200315T160000Z\r\nDTEND:20200315T160416Z\r\n"));
+
+	// Long lines are folded, and special characters escaped.
+	assert!(ics.lines().all(|line| line.len() <= 75));
+	assert!(ics.contains("Why Are We Here?\\n\\nThe first episode"));
+}
+
+#[test]
+fn rss_feed() {
+	let episodes = fixture_episodes();
+	let mut feed = Feed::new(
+		"Red vs. Blue",
+		"https://roosterteeth.com/series/red-vs-blue",
+		"Red & Blue",
+		&episodes,
+	);
+	feed.podcast = Some(Podcast {
+		author: String::from("Rooster Teeth"),
+		category: String::from("Comedy"),
+		explicit: true,
+		image: None,
+	});
+	let video: videos::Root =
+		serde_json::from_str(include_str!("../fixtures/videos.json")).unwrap();
+	feed.media
+		.insert(episodes[0].uuid, Enclosure::from_video(&video.data[0]));
+	let rss = feed.to_rss();
+
+	assert_eq!(rss.matches("<item>").count(), 2);
+	assert!(rss.contains("<description>Red &amp; Blue</description>"));
+	assert!(
+		rss.contains("<link>https://roosterteeth.com/watch/red-vs-blue-season-1-episode-1</link>")
+	);
+	assert!(rss.contains("<pubDate>Sun, 15 Mar 2020 16:00:00 +0000</pubDate>"));
+	assert!(rss.contains("<itunes:duration>256</itunes:duration>"));
+	// Podcast items only enclose media, their artwork is in itunes:image.
+	assert_eq!(rss.matches("<enclosure").count(), 1);
+	assert!(rss.contains("<enclosure url=\"https://rtv3-video.roosterteeth.com/store/48bb3e93/mp4/video.mp4\" length=\"0\" type=\"video/mp4\"/>"));
+	assert!(rss.contains(
+		"<itunes:image href=\"https://cdn.ruv.wtf/uploads/images/rvb-1-1/original/ep.jpg\"/>"
+	));
+
+	feed.podcast = None;
+	feed.media.clear();
+	let rss = feed.to_rss();
+	assert!(rss.contains("<enclosure url=\"https://cdn.ruv.wtf/uploads/images/rvb-1-1/original/ep.jpg\" length=\"0\" type=\"image/jpeg\"/>"));
+}
+
+#[test]
+fn atom_feed() {
+	let episodes = fixture_episodes();
+	let atom = Feed::new(
+		"Red vs. Blue",
+		"https://roosterteeth.com/series/red-vs-blue",
+		"",
+		&episodes,
+	)
+	.to_atom();
+
+	assert!(atom.contains("<updated>2020-03-22T16:00:00+00:00</updated>"));
+	assert!(atom.contains("<id>urn:uuid:ffac2c4f-464d-11e7-a302-065410f210c4</id>"));
+	assert!(atom.contains("Sarge shows off the new arrival &amp; Church makes a bold claim."));
+	assert!(!atom.contains("itunes"));
+}
+
+#[test]
+fn hls_playlists() {
+	let base = "https://rtv3-video.roosterteeth.com/store/abc/ts/index.m3u8";
+	let master = "#EXTM3U
+#EXT-X-VERSION:3
+#EXT-X-STREAM-INF:BANDWIDTH=5128000,RESOLUTION=1920x1080,CODECS=\"avc1.640028,mp4a.40.2\"
+1080p.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=1128000,RESOLUTION=640x360,CODECS=\"avc1.4d401e,mp4a.40.2\"
+https://cdn.example.com/360p.m3u8
+";
+	let variants = hls::parse_master_playlist(base, master);
+
+	assert_eq!(variants.len(), 2);
+	assert_eq!(variants[0].height(), Some(360));
+	assert_eq!(variants[1].codecs.as_deref(), Some("avc1.640028,mp4a.40.2"));
+	assert_eq!(
+		&variants[1].url,
+		"https://rtv3-video.roosterteeth.com/store/abc/ts/1080p.m3u8"
+	);
+
+	let media = "#EXTM3U
+#EXT-X-TARGETDURATION:10
+#EXTINF:10.0,
+1080p-00001.ts
+#EXTINF:4.2,
+1080p-00002.ts
+#EXT-X-ENDLIST
+";
+	let segments = hls::parse_media_playlist(&variants[1].url, media);
+
+	assert_eq!(
+		segments,
+		vec![
+			"https://rtv3-video.roosterteeth.com/store/abc/ts/1080p-00001.ts",
+			"https://rtv3-video.roosterteeth.com/store/abc/ts/1080p-00002.ts",
+		]
+	);
+}
+
+#[test]
+fn round_trip_channels() {
+	let json = round_trip::<channels::Root>(include_str!("../fixtures/channels.json"));
+
+	assert_eq!(json["data"][0]["type"], "channel");
+	assert_eq!(
+		json["data"][0]["links"]["self"],
+		"/api/v1/channels/rooster-teeth"
+	);
+}
+
+#[test]
+fn round_trip_episodes() {
+	let json = round_trip::<episodes::Root>(include_str!("../fixtures/episodes.json"));
+
+	assert_eq!(
+		json["data"][0]["_index"],
+		"episodes-production-en_20200108090014250"
+	);
+	assert_eq!(json["data"][0]["included"]["tags"][0]["type"], "tag");
+}
+
+#[test]
+fn round_trip_series() {
+	let json = round_trip::<series::Root>(include_str!("../fixtures/series.json"));
+
+	assert_eq!(
+		json["data"][0]["canonical_links"]["self"],
+		"/series/red-vs-blue"
+	);
+}
+
+#[test]
+fn round_trip_seasons() {
+	let json = round_trip::<seasons::Root>(include_str!("../fixtures/seasons.json"));
+
+	assert_eq!(
+		json["data"][1]["attributes"]["episodes_available"]["member"],
+		false
+	);
+}
+
+#[test]
+fn round_trip_users() {
+	let json = round_trip::<users::Root>(include_str!("../fixtures/users.json"));
+	assert_eq!(json["data"][0]["attributes"]["member_tier"], "first");
+
+	let user: users::Root = serde_json::from_str(include_str!("../fixtures/users.json")).unwrap();
+	assert_eq!(user.data[0].tier(), Tier::Sponsor);
+}
+
+#[test]
+fn round_trip_watch_times() {
+	let json = round_trip::<users::ProgressRoot>(include_str!("../fixtures/watch_times.json"));
+	assert_eq!(json["data"][0]["value"], 120);
+
+	let progress: users::ProgressRoot =
+		serde_json::from_str(include_str!("../fixtures/watch_times.json")).unwrap();
+	let mut episode = fixture_episodes().remove(0);
+	assert_eq!(progress.data[0].episode_id, episode.uuid);
+	assert!(!progress.data[0].is_finished(&episode));
+
+	episode.attributes.length = 140;
+	assert!(progress.data[0].is_finished(&episode));
+}
+
+#[test]
+fn anonymous_user_endpoints() {
+	let requests = Requests::new(Credential::Anonymous);
+	let episode = fixture_episodes().remove(0);
+
+	assert_eq!(requests.get_profile().unwrap_err(), UserError::NotLoggedIn);
+	assert_eq!(
+		requests.get_watchlist().unwrap_err(),
+		UserError::NotLoggedIn
+	);
+	assert!(requests.add_to_watchlist(&episode.uuid).is_err());
+	assert!(requests.remove_from_watchlist(&episode.uuid).is_err());
+	assert!(requests.get_watch_progress(&episode.uuid).is_err());
+	assert!(requests.list_watch_progress().is_err());
+	assert!(requests.set_watch_progress(&episode.uuid, 60).is_err());
+}
+
+#[test]
+fn round_trip_videos() {
+	let json = round_trip::<videos::Root>(include_str!("../fixtures/videos.json"));
+
+	assert_eq!(json["data"][0]["_score"], 1.0);
+	assert_eq!(
+		json["data"][0]["attributes"]["ad_config"]["midroll"],
+		serde_json::json!([])
+	);
+}
+
+#[test]
+fn image_selection() {
+	let root: series::Root = serde_json::from_str(include_str!("../fixtures/series.json")).unwrap();
+	let images = &root.data[0].included.images;
+
+	assert_eq!(best_image(images, "poster", None).unwrap().id, 7);
+	assert_eq!(
+		best_image(images, "poster", Some("landscape")).unwrap().id,
+		7
+	);
+	assert_eq!(
+		best_image(images, "thumbnail", Some("landscape"))
+			.unwrap()
+			.id,
+		8
+	);
+	assert!(best_image(images, "profile", None).is_none());
+
+	assert_eq!(Size::for_width(400), Size::Small);
+	assert_eq!(Size::for_width(4000), Size::Large);
+	assert_eq!(
+		images[1].attributes.url_for_width(1000),
+		"https://cdn.ruv.wtf/uploads/images/red-vs-blue/md/cover.jpg"
+	);
+}
+
+#[test]
+fn typed_identifiers() {
+	let episodes = fixture_episodes();
+	let episode = &episodes[0];
+
+	assert_eq!(episode.id, EpisodeId(23242));
+	assert_eq!(episode.uuid, "FFAC28DC-464D-11E7-A302-065410F210C4");
+	assert_eq!(
+		episode.uuid.to_string(),
+		"ffac28dc-464d-11e7-a302-065410f210c4"
+	);
+	assert_eq!(episode.included.tags[0].id, TagId(1841));
+	assert_eq!(
+		episode.attributes.show_slug,
+		"red-vs-blue".parse::<ShowSlug>().unwrap()
+	);
+
+	assert!("".parse::<ShowSlug>().is_err());
+	assert!("red-vs-blue/seasons".parse::<ShowSlug>().is_err());
+	assert!("12ab".parse::<EpisodeId>().is_err());
+}
+
+#[test]
+fn parse_urls() {
+	assert_eq!(
+		Resource::from_url("https://roosterteeth.com/watch/red-vs-blue-season-1-episode-1"),
+		Ok(Resource::Episode(
+			"red-vs-blue-season-1-episode-1".parse().unwrap()
+		))
+	);
+	assert_eq!(
+		Resource::from_url("/series/red-vs-blue"),
+		Ok(Resource::Series("red-vs-blue".parse().unwrap()))
+	);
+	assert_eq!(
+		Resource::from_url("https://www.roosterteeth.com/series/red-vs-blue/?season=2"),
+		Ok(Resource::Season {
+			show: "red-vs-blue".parse().unwrap(),
+			number: 2
+		})
+	);
+	assert_eq!(
+		Resource::from_url("roosterteeth.com/channel/achievement-hunter").map(|r| r.url()),
+		Ok(String::from(
+			"https://roosterteeth.com/channel/achievement-hunter"
+		))
+	);
+
+	assert!(
+		Resource::from_url("https://example.com/watch/red-vs-blue-season-1-episode-1").is_err()
+	);
+	assert!(Resource::from_url("https://roosterteeth.com/schedule").is_err());
+}
+
+#[test]
+fn round_trip_livestreams() {
+	let json = round_trip::<livestreams::Root>(include_str!("../fixtures/livestreams.json"));
+
+	assert_eq!(
+		json["data"][1]["attributes"]["url"],
+		serde_json::Value::Null
+	);
+}
+
+#[test]
+fn livestream_events() {
+	let root: livestreams::Root =
+		serde_json::from_str(include_str!("../fixtures/livestreams.json")).unwrap();
+	let mut streams = root.data;
+
+	assert_eq!(
+		streams[0].hls_url(),
+		Some("https://live.roosterteeth.com/rt-podcast-live/index.m3u8")
+	);
+	assert_eq!(streams[1].hls_url(), None);
+
+	let mut monitor = Monitor::new();
+	assert_eq!(
+		monitor.update(&streams),
+		vec![Event::Started(Box::new(streams[0].clone()))]
+	);
+	assert!(monitor.update(&streams).is_empty());
+
+	streams[0].attributes.is_live = false;
+	assert_eq!(
+		monitor.update(&streams),
+		vec![Event::Ended("rt-podcast-live".parse().unwrap())]
+	);
+	assert_eq!(monitor.live().count(), 0);
+}
+
+#[test]
+fn round_trip_product_collections() {
+	let json = round_trip::<products::CollectionRoot>(include_str!(
+		"../fixtures/product_collections.json"
+	));
+
+	assert_eq!(
+		json["data"][0]["included"]["products"][0]["attributes"]["price"],
+		"$25.00"
+	);
+}
+
+#[test]
+fn round_trip_featured_items() {
+	let json = round_trip::<featured_items::Root>(include_str!("../fixtures/featured_items.json"));
+
+	assert_eq!(json["data"][0]["attributes"]["link"], "/series/red-vs-blue");
+}
+
+#[test]
+fn round_trip_bonus_features() {
+	let json = round_trip::<series::BonusRoot>(include_str!("../fixtures/bonus_features.json"));
+	assert_eq!(json["data"][1]["type"], "video");
+
+	let root: series::BonusRoot =
+		serde_json::from_str(include_str!("../fixtures/bonus_features.json")).unwrap();
+	assert!(
+		matches!(&root.data[0], series::BonusFeature::Episode(episode)
+		if episode.attributes.title == "Behind the Scenes: Blood Gulch")
+	);
+	assert!(matches!(root.data[1], series::BonusFeature::Video(_)));
+	assert!(matches!(root.data[2], series::BonusFeature::Other(_)));
+
+	let episodes: Vec<_> = root
+		.data
+		.into_iter()
+		.filter_map(series::BonusFeature::into_episode)
+		.collect();
+	assert_eq!(episodes.len(), 1);
+}
+
+#[test]
+fn follow_bonus_and_related_links() {
+	let series = fixture_catalog().series.remove(0);
+	let episode = fixture_episodes().remove(0);
+	let interaction = |url: &str, body: &str| Interaction {
+		method: String::from("GET"),
+		url: format!("https://svod-be.roosterteeth.com{}", url),
+		status: 200,
+		headers: Default::default(),
+		body: Body::Text(body.to_string()),
+	};
+	// A full page from an endpoint that ignores the page, and doesn't say how many there are.
+	let mut popular = episode.clone();
+	popular.links.related_shows = String::from("/api/v1/shows/popular/related");
+	let full_page = serde_json::json!({ "data": vec![&series; 100] }).to_string();
+
+	let cassette = Cassette {
+		interactions: vec![
+			interaction(
+				&format!("{}?per_page=100&page=1", series.links.bonus_features),
+				include_str!("../fixtures/bonus_features.json"),
+			),
+			interaction(
+				&format!("{}?per_page=100&page=1", series.links.related),
+				include_str!("../fixtures/series.json"),
+			),
+			interaction(
+				&format!("{}?per_page=100&page=1", popular.links.related_shows),
+				&full_page,
+			),
+			interaction(
+				&format!("{}?per_page=100&page=2", popular.links.related_shows),
+				&full_page,
+			),
+		],
+	};
+	let requests = Requests::new(Credential::Anonymous).with_transport(Replayer::new(cassette));
+
+	// The first page is the only one, so the second is never requested.
+	assert_eq!(requests.get_bonus_features(&series).len(), 3);
+	let related = requests.get_related_shows(&series);
+	assert_eq!(related, fixture_catalog().series);
+	assert_eq!(episode.links.related_shows, series.links.related);
+	assert_eq!(requests.get_episode_related_shows(&episode), related);
+
+	// Getting the same page twice ends the list, instead of repeating it forever.
+	assert_eq!(requests.get_episode_related_shows(&popular).len(), 100);
+}
+
+#[test]
+fn offline_search() {
+	let catalog = fixture_catalog();
+
+	let hits = catalog.search("Red vs. Blue", search::Kind::All);
+	assert!(matches!(hits[0].item, Item::Series(_)));
+	assert_eq!(hits.len(), 5);
+
+	// Cast members and tags are searchable, and words only need to be a prefix.
+	let hits = catalog.search("burn", search::Kind::Episodes);
+	assert_eq!(hits.len(), 2);
+	let hits = catalog.search("halo burnie", search::Kind::All);
+	assert_eq!(hits.len(), 1);
+	assert_eq!(hits[0].item.title(), "Episode 1: Why Are We Here?");
+
+	assert!(catalog.search("zeppelin", search::Kind::Shows).is_empty());
+	assert!(catalog.search("   ", search::Kind::All).is_empty());
+}
+
+#[test]
+fn mixed_search_results() {
+	let series: serde_json::Value =
+		serde_json::from_str(include_str!("../fixtures/series.json")).unwrap();
+	let seasons: serde_json::Value =
+		serde_json::from_str(include_str!("../fixtures/seasons.json")).unwrap();
+	let episodes: serde_json::Value =
+		serde_json::from_str(include_str!("../fixtures/episodes.json")).unwrap();
+	let mut broken = episodes["data"][1].clone();
+	broken["attributes"] = serde_json::json!({ "title": "Missing fields" });
+	let json = serde_json::json!({
+		"data": [
+			series["data"][0],
+			seasons["data"][0],
+			episodes["data"][0],
+			{ "type": "podcast", "id": 12 },
+			broken,
+		],
+		"page": 1,
+		"per_page": 100,
+		"total_pages": 1,
+		"total_results": 5,
+	});
+
+	let root: search::Root = serde_json::from_value(json).unwrap();
+
+	assert!(matches!(root.data[0], SearchResult::Series(_)));
+	assert!(matches!(root.data[1], SearchResult::Season(_)));
+	assert!(matches!(root.data[2], SearchResult::Episode(_)));
+	// Unexpected results are kept as they are instead of failing the whole page.
+	assert!(matches!(&root.data[3], SearchResult::Other(value) if value["type"] == "podcast"));
+	assert!(matches!(root.data[4], SearchResult::Other(_)));
+
+	// Results without a type aren't guessed from their fields.
+	let mut untyped = episodes["data"][0].clone();
+	untyped.as_object_mut().unwrap().remove("type");
+	assert!(matches!(
+		serde_json::from_value(untyped).unwrap(),
+		SearchResult::Other(_)
+	));
+
+	round_trip::<search::Root>(&serde_json::to_string(&root).unwrap());
+}
+
+#[test]
+fn catalog_save_and_load() {
+	let catalog = fixture_catalog();
+	let path = std::env::temp_dir().join(format!(
+		"roosterteeth-rs-catalog-{}.json",
+		std::process::id()
+	));
+
+	catalog.save(&path).unwrap();
+	let loaded = Catalog::load(&path).unwrap();
+	std::fs::remove_file(&path).unwrap();
+
+	assert_eq!(loaded, catalog);
+	assert_eq!(
+		loaded
+			.episodes_of(&"red-vs-blue-season-1".parse().unwrap())
+			.count(),
+		2
+	);
+}
+
+#[test]
+fn browse_tags_and_cast() {
+	let mut catalog = fixture_catalog();
+
+	let halo: TagSlug = "halo".parse().unwrap();
+	assert_eq!(catalog.episodes_tagged(&halo).count(), 1);
+
+	let tags = catalog.tags();
+	assert_eq!(tags.len(), 1);
+	assert_eq!(tags[0].0.attributes.tag, "Halo");
+	assert_eq!(tags[0].1, 1);
+
+	let members = catalog.cast_members();
+	let names: Vec<(&str, usize)> = members
+		.iter()
+		.map(|(member, count)| (member.attributes.name.as_str(), *count))
+		.collect();
+	assert_eq!(names, vec![("Burnie Burns", 2), ("Gustavo Sorola", 1)]);
+
+	// Appearances in another show are grouped separately, ordered by show title.
+	let mut other = catalog.episodes[0].clone();
+	other.attributes.show_title = "Achievement Hunter".to_string();
+	other.attributes.show_slug = "achievement-hunter".parse().unwrap();
+	catalog.episodes.push(other);
+
+	let filmography = catalog.filmography("212".parse().unwrap()).unwrap();
+	assert_eq!(filmography.member.attributes.name, "Burnie Burns");
+	assert_eq!(filmography.episode_count(), 3);
+	let shows: Vec<(&str, usize)> = filmography
+		.credits
+		.iter()
+		.map(|credit| (credit.show_title, credit.episodes.len()))
+		.collect();
+	assert_eq!(shows, vec![("Achievement Hunter", 1), ("Red vs. Blue", 2)]);
+
+	assert!(catalog.filmography("1".parse().unwrap()).is_none());
+}
+
+#[test]
+fn access_checks() {
+	let episodes = fixture_episodes();
+	let users: users::Root = serde_json::from_str(include_str!("../fixtures/users.json")).unwrap();
+	let sponsor = &users.data[0];
+	let mut member = sponsor.clone();
+	member.attributes.is_sponsor = false;
+	member.attributes.member_tier_i = 0;
+
+	let now = utc("2020-03-10T00:00:00Z");
+	assert_eq!(can_watch(&episodes[0], Some(sponsor), None, now), Ok(()));
+	assert_eq!(
+		can_watch(&episodes[0], None, "US".parse().ok(), now),
+		Err(VideoUnavailable::NotYetPublic(Some(
+			episodes[0].attributes.public_golive_at
+		)))
+	);
+	assert_eq!(
+		can_watch(
+			&episodes[1],
+			Some(&member),
+			None,
+			utc("2020-04-01T00:00:00Z")
+		),
+		Err(VideoUnavailable::SponsorOnly)
+	);
+	assert_eq!(
+		can_watch(
+			&episodes[1],
+			Some(sponsor),
+			"de".parse().ok(),
+			utc("2020-04-01T00:00:00Z")
+		),
+		Err(VideoUnavailable::GeoBlocked("DE".parse().ok()))
+	);
+
+	// Logging in is enough once an episode has gone live for members.
+	let mut early = episodes[0].clone();
+	early.attributes.member_golive_at = early.attributes.sponsor_golive_at;
+	assert_eq!(
+		can_watch(&early, None, None, now),
+		Err(VideoUnavailable::NotLoggedIn)
+	);
+	assert_eq!(can_watch(&early, Some(&member), None, now), Ok(()));
+}
+
+#[test]
+fn video_denials() {
+	assert_eq!(
+		denial(StatusCode::UNAUTHORIZED, ""),
+		VideoUnavailable::NotLoggedIn
+	);
+	assert_eq!(
+		denial(
+			StatusCode::FORBIDDEN,
+			r#"{"access":false,"message":"This content is not available in your region"}"#
+		),
+		VideoUnavailable::GeoBlocked(None)
+	);
+	assert_eq!(
+		denial(
+			StatusCode::FORBIDDEN,
+			r#"{"error":"Content is only available to FIRST members (sponsors)"}"#
+		),
+		VideoUnavailable::SponsorOnly
+	);
+	assert_eq!(
+		denial(StatusCode::FORBIDDEN, "Video is not yet available"),
+		VideoUnavailable::NotYetPublic(None)
+	);
+	assert_eq!(
+		denial(StatusCode::NOT_FOUND, "Not Found"),
+		VideoUnavailable::Other(404)
+	);
+}
+
+#[test]
+fn geo_restrictions() {
+	let country: Country = " au".parse().unwrap();
+	assert_eq!(country, "AU".parse().unwrap());
+	assert_eq!(serde_json::to_value(country).unwrap(), "AU");
+	assert!("AUS".parse::<Country>().is_err());
+	assert!("ZZ".parse::<Country>().is_err());
+
+	let episodes = fixture_episodes();
+	assert_eq!(
+		episodes[1].blocked_countries(),
+		vec!["DE".parse().unwrap(), "AU".parse().unwrap()]
+	);
+	assert_eq!(geo::viewable_in(episodes.clone(), country).len(), 1);
+	assert_eq!(geo::viewable_in(episodes, "US".parse().unwrap()).len(), 2);
+
+	let catalog = fixture_catalog();
+	let blocked = catalog.blocked();
+	assert_eq!(blocked.len(), 1);
+	assert_eq!(blocked[0].item.title(), "Episode 2: Red Zeppelin Rules");
+
+	let report = catalog.blocked_by_country();
+	let countries: Vec<String> = report.keys().map(Country::to_string).collect();
+	assert_eq!(countries, vec!["AU", "DE"]);
+}
+
+#[test]
+fn nfo_export() {
+	let catalog = fixture_catalog();
+	let series = &catalog.series[0];
+	let channel = &catalog.channels[0];
+
+	let show = nfo::tvshow(series, &catalog.episodes, Some(channel));
+	assert!(show.contains("<title>Red vs. Blue</title>"));
+	assert!(show.contains("<premiered>2017-06-01</premiered>"));
+	assert!(show.contains("<genre>Full of Laughs</genre>"));
+	assert!(show.contains(&format!("<studio>{}</studio>", channel.attributes.name)));
+	// Cast members appearing in several episodes are only listed once.
+	assert_eq!(show.matches("<actor>").count(), 2);
+
+	let season = nfo::season(&catalog.seasons[0]);
+	assert!(season.contains("<seasonnumber>1</seasonnumber>"));
+	assert!(season.contains("<premiered>2003-04-01</premiered>"));
+
+	let episode = nfo::episode(&catalog.episodes[1], None);
+	assert!(episode.starts_with("<?xml"));
+	assert!(episode.contains("<season>1</season>"));
+	assert!(episode.contains("<episode>2</episode>"));
+	assert!(episode
+		.contains("<plot>Sarge shows off the new arrival &amp; Church makes a bold claim.</plot>"));
+	assert!(episode.contains("<aired>2003-04-08</aired>"));
+	assert!(episode.contains("<runtime>4</runtime>"));
+	assert!(episode.contains("\t\t<name>Gustavo Sorola</name>\n\t\t<order>1</order>"));
+	assert!(!episode.contains("<studio>"));
+
+	let artwork: Vec<String> = nfo::series_artwork(series)
+		.iter()
+		.map(nfo::Artwork::file_name)
+		.collect();
+	assert_eq!(artwork, vec!["poster.jpg", "fanart.jpg"]);
+	let thumbs = nfo::episode_artwork(&catalog.episodes[0], "S01E01");
+	assert_eq!(thumbs[0].file_name(), "S01E01-thumb.jpg");
+	assert!(nfo::episode_artwork(&catalog.episodes[1], "S01E02").is_empty());
+}
+
+#[test]
+fn plex_layout() {
+	let catalog = fixture_catalog();
+	let series = &catalog.series[0];
+
+	let mut bonus = catalog.episodes[0].clone();
+	bonus.attributes.slug = "red-vs-blue-behind-the-scenes".parse().unwrap();
+	bonus.attributes.title = String::from("Behind the Scenes");
+	let bonus = vec![bonus];
+
+	let plan = Plan::new(series, &catalog.episodes, &bonus);
+	let paths: Vec<String> = plan
+		.entries
+		.iter()
+		.map(|entry| entry.path.to_string_lossy().into_owned())
+		.collect();
+	assert_eq!(
+		paths,
+		vec![
+			"Red vs. Blue (2003)/Season 00/Red vs. Blue (2003) - s00e01 - Behind the Scenes",
+			"Red vs. Blue (2003)/Season 01/Red vs. Blue (2003) - s01e01 - Episode 1_ Why Are We Here_",
+			"Red vs. Blue (2003)/Season 01/Red vs. Blue (2003) - s01e02 - Episode 2_ Red Zeppelin Rules",
+		]
+	);
+	assert!(plan.entries[0].is_special());
+	assert!(plan.collisions().is_empty());
+
+	// Shows listed newest first are put back in release order.
+	let mut descending = series.clone();
+	descending.attributes.episode_order = String::from("desc");
+	let mut episodes = catalog.episodes.clone();
+	episodes.swap(0, 1);
+	episodes[0].attributes.sort_number = 1;
+	episodes[1].attributes.sort_number = 2;
+	let plan = Plan::new(&descending, &episodes, &[]);
+	assert_eq!(plan.entries[0].number, 1);
+
+	episodes[1].attributes.number = 2;
+	let mut plan = Plan::new(series, &episodes, &[]);
+	let collisions = plan.collisions();
+	assert_eq!(collisions.len(), 1);
+	assert_eq!(collisions[0].episodes.len(), 2);
+
+	plan.renumber();
+	assert!(plan.collisions().is_empty());
+	let numbers: Vec<u16> = plan.entries.iter().map(|entry| entry.number).collect();
+	assert_eq!(numbers, vec![1, 2]);
+
+	let manifest = plan.manifest();
+	assert_eq!(
+		manifest.path_of(&episodes[0].attributes.slug),
+		plan.path_of(&episodes[0].attributes.slug)
+	);
+
+	// Once saved, episodes keep their paths even when the API numbers them differently.
+	episodes[1].attributes.number = 7;
+	let mut plan = Plan::new(series, &episodes, &[]);
+	plan.keep_paths(&manifest);
+	assert_eq!(plan.entries[1].number, 2);
+	assert_eq!(plan.manifest().entries, manifest.entries);
+	assert!(plan.manifest().collisions.is_empty());
+
+	// A kept number can clash with the number of another episode.
+	let mut clashing = manifest.clone();
+	clashing.entries.remove(1);
+	clashing.entries[0].number = 7;
+	let mut plan = Plan::new(series, &episodes, &[]);
+	plan.keep_paths(&clashing);
+	assert_eq!(plan.manifest().collisions.len(), 1);
+
+	// Shows listing their newest season first are planned in that order.
+	let mut descending = series.clone();
+	descending.attributes.season_order = String::from("desc");
+	let mut episodes = catalog.episodes.clone();
+	episodes[1].attributes.season_number = 2;
+	let plan = Plan::new(&descending, &episodes, &[]);
+	let seasons: Vec<u16> = plan.entries.iter().map(|entry| entry.season).collect();
+	assert_eq!(seasons, vec![2, 1]);
+
+	assert_eq!(layout::sanitize(" AC/DC: Live. "), "AC_DC_ Live");
+}
+
+#[test]
+fn export_rows() {
+	let episodes = fixture_episodes();
+
+	let mut csv = Csv::new(Vec::new());
+	let rows =
+		export::write::<episodes::Episode, _, _>(&episodes, Nested::Joined, &mut csv).unwrap();
+	assert_eq!(rows, 2);
+
+	let columns = export::columns::<episodes::Episode>(Nested::Joined);
+	assert_eq!(columns.last().unwrap().name, "blacklisted_countries");
+
+	let joined = export::rows(&episodes[0], Nested::Joined);
+	let genres = columns
+		.iter()
+		.position(|column| column.name == "genres")
+		.unwrap();
+	assert_eq!(
+		joined[0][genres],
+		export::Value::from("Action Packed|Full of Laughs")
+	);
+
+	// A row per tag, genre, cast member and blacklisted country.
+	let exploded = export::rows(&episodes[1], Nested::Exploded);
+	assert_eq!(exploded.len(), 5);
+	assert!(exploded.iter().any(|row| row[row.len() - 2..]
+		== [
+			export::Value::from("blacklisted_countries"),
+			export::Value::from("DE")
+		]));
+
+	let channels: channels::Root =
+		serde_json::from_str(include_str!("../fixtures/channels.json")).unwrap();
+	let mut jsonl = JsonLines::new(Vec::new());
+	let rows =
+		export::write::<channels::Channel, _, _>(&channels.data, Nested::Exploded, &mut jsonl)
+			.unwrap();
+	let output = String::from_utf8(csv.into_inner()).unwrap();
+	assert!(output.starts_with("id,uuid,slug,title,"));
+	assert_eq!(output.lines().count(), 3);
+
+	let jsonl = String::from_utf8(jsonl.into_inner()).unwrap();
+	assert_eq!(jsonl.lines().count(), rows);
+	let first: serde_json::Value = serde_json::from_str(jsonl.lines().next().unwrap()).unwrap();
+	assert_eq!(first["slug"], channels.data[0].attributes.slug.as_str());
+
+	// Pages stop at the first empty page.
+	let mut pages = Pages::new(|page| if page < 3 { vec![page; 2] } else { Vec::new() });
+	assert_eq!(pages.by_ref().collect::<Vec<_>>(), vec![1, 1, 2, 2]);
+	assert_eq!(pages.pages_fetched(), 3);
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn export_parquet() {
+	use parquet::file::reader::{FileReader, SerializedFileReader};
+
+	let path = std::env::temp_dir().join("roosterteeth-rs-export.parquet");
+	let mut writer = export::Parquet::new(std::fs::File::create(&path).unwrap());
+	let rows =
+		export::write::<episodes::Episode, _, _>(fixture_episodes(), Nested::Exploded, &mut writer)
+			.unwrap();
+
+	let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+	let metadata = reader.metadata();
+	assert_eq!(metadata.file_metadata().num_rows() as usize, rows);
+	assert_eq!(
+		metadata.file_metadata().schema_descr().num_columns(),
+		export::columns::<episodes::Episode>(Nested::Exploded).len()
+	);
+
+	std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn catalog_diff() {
+	let old = fixture_catalog();
+	assert!(old.diff(&old).is_empty());
+
+	let mut new = old.clone();
+	let removed = new.episodes.remove(1);
+	let mut added = removed.clone();
+	added.attributes.slug = "red-vs-blue-season-1-episode-3".parse().unwrap();
+	new.episodes.push(added);
+
+	let episode = &mut new.episodes[0].attributes;
+	episode.title = String::from("Why Are We Here?");
+	episode.is_sponsors_only = !episode.is_sponsors_only;
+	episode.public_golive_at += chrono::Duration::days(1);
+	episode.blacklisted_countries = vec![String::from("DE")];
+
+	let diff = old.diff(&new);
+	assert!(diff.shows.is_empty() && diff.seasons.is_empty());
+	assert_eq!(
+		diff.episodes.added,
+		vec![new.episodes[1].attributes.slug.clone()]
+	);
+	assert_eq!(diff.episodes.removed, vec![removed.attributes.slug]);
+
+	let changes = &diff.episodes.changed[0].changes;
+	assert_eq!(changes.len(), 4);
+	assert!(matches!(
+		changes[1],
+		Change::GoLive {
+			tier: Tier::Public,
+			..
+		}
+	));
+	assert_eq!(
+		changes[3],
+		Change::BlacklistedCountries {
+			added: vec!["DE".parse().unwrap()],
+			removed: Vec::new(),
+		}
+	);
+
+	let json = serde_json::to_value(&diff).unwrap();
+	assert_eq!(
+		json["episodes"]["changed"][0]["changes"][2]["field"],
+		"sponsors_only"
+	);
+	assert_eq!(serde_json::from_value::<Diff>(json).unwrap(), diff);
+
+	let text = diff.to_string();
+	assert!(text.contains("Episodes: 1 added, 1 removed, 1 changed"));
+	assert!(text.contains("blacklisted countries: +DE"));
+}
+
+#[cfg(feature = "serve")]
+#[test]
+fn serve_catalog() {
+	use crate::serve::{respond, Server};
+
+	let catalog = fixture_catalog();
+
+	let response = respond(&catalog, "/api/v1/shows?per_page=1&page=1&order=desc");
+	assert_eq!(response.status, 200);
+	let shows: series::Root = serde_json::from_str(&response.body).unwrap();
+	assert_eq!(shows.data.len(), 1);
+	assert_eq!(shows.total_results, Some(catalog.series.len() as u32));
+
+	let response = respond(
+		&catalog,
+		"/api/v1/seasons/red-vs-blue-season-1/episodes?order=desc",
+	);
+	let episodes: episodes::Root = serde_json::from_str(&response.body).unwrap();
+	assert_eq!(episodes.data[0], catalog.episodes[1]);
+
+	assert_eq!(respond(&catalog, "/api/v1/watch/missing").status, 404);
+	assert_eq!(
+		respond(&catalog, "/api/v1/watch/missing/videos").status,
+		404
+	);
+	assert_eq!(respond(&catalog, "/elsewhere").status, 404);
+
+	// The client is pointed at the mirror instead of the RoosterTeeth api.
+	let server = Server::bind("127.0.0.1:0", catalog.clone())
+		.unwrap()
+		.spawn();
+	let requests = Requests::new(Credential::Anonymous).with_api_url(server.api_url());
+
+	assert_eq!(requests.list_channels(), catalog.channels);
+	assert_eq!(
+		requests.get_episode(&catalog.episodes[0].attributes.slug),
+		catalog.episodes[0]
+	);
+	let synced = requests.sync_catalog(None);
+	assert_eq!(synced.series, catalog.series);
+	assert_eq!(synced.episodes, catalog.episodes);
+	assert_eq!(
+		requests.iter_episodes(None, Some("asc")).count(),
+		catalog.episodes.len()
+	);
+}
+
+#[test]
+fn archive_responses() {
+	let dir = std::env::temp_dir().join(format!("roosterteeth-rs-archive-{}", std::process::id()));
+	let archive = Archive::open(&dir).unwrap();
+	let body = include_str!("../fixtures/channels.json");
+	let url = "https://svod-be.roosterteeth.com/api/v1/channels";
+
+	let first = archive
+		.store("GET", url, 200, Default::default(), body.as_bytes())
+		.unwrap();
+	let second = archive
+		.store("GET", url, 200, Default::default(), body.as_bytes())
+		.unwrap();
+	archive
+		.store("GET", url, 500, Default::default(), b"{}")
+		.unwrap();
+
+	// Identical bodies are only stored once, but every response is indexed.
+	assert_eq!(first.sha256, second.sha256);
+	assert_eq!(archive.records().unwrap().len(), 3);
+	assert_eq!(archive.latest(url).unwrap(), Some(second.clone()));
+
+	let raw: serde_json::Value = archive.load(&first).unwrap();
+	assert_eq!(
+		raw,
+		serde_json::from_str::<serde_json::Value>(body).unwrap()
+	);
+	let typed: channels::Root = archive.rehydrate(url).unwrap().unwrap();
+	assert_eq!(typed.data.len(), raw["data"].as_array().unwrap().len());
+	assert!(archive
+		.rehydrate::<channels::Root>("https://example.com")
+		.unwrap()
+		.is_none());
+
+	std::fs::write(
+		dir.join("objects")
+			.join(&first.sha256[..2])
+			.join(&first.sha256),
+		"tampered",
+	)
+	.unwrap();
+	assert!(archive.body(&first).is_err());
+
+	// Failing to archive doesn't stop the request, the error is kept instead.
+	std::fs::remove_dir_all(&dir).unwrap();
+	std::fs::write(&dir, "not a directory").unwrap();
+	let cassette = Cassette {
+		interactions: vec![Interaction {
+			method: String::from("GET"),
+			url: url.to_string(),
+			status: 200,
+			headers: Default::default(),
+			body: Body::Text(body.to_string()),
+		}],
+	};
+	let requests = Requests::new(Credential::Anonymous)
+		.with_transport(Replayer::new(cassette))
+		.with_archive(archive);
+	assert_eq!(requests.list_channels(), fixture_catalog().channels);
+	assert!(requests.take_archive_error().is_some());
+	assert!(requests.take_archive_error().is_none());
+
+	std::fs::remove_file(&dir).unwrap();
+}
+
+#[cfg(feature = "serve")]
+#[test]
+fn archive_requests() {
+	use crate::serve::Server;
+
+	let dir = std::env::temp_dir().join(format!(
+		"roosterteeth-rs-archive-requests-{}",
+		std::process::id()
+	));
+	let catalog = fixture_catalog();
+	let server = Server::bind("127.0.0.1:0", catalog.clone())
+		.unwrap()
+		.spawn();
+	let requests = Requests::new(Credential::Anonymous)
+		.with_api_url(server.api_url())
+		.with_archive(Archive::open(&dir).unwrap());
+
+	let channels = requests.list_channels();
+	let archive = requests.archive().unwrap();
+	let record = archive
+		.latest(&format!("{}/channels", server.api_url()))
+		.unwrap()
+		.unwrap();
+	assert_eq!(record.method, "GET");
+	assert_eq!(record.headers["content-type"], "application/json");
+
+	let rehydrated: channels::Root = archive.load(&record).unwrap();
+	assert_eq!(rehydrated.data, channels);
+
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn replay_cassette() {
+	let interaction = |url: &str, body: &str| Interaction {
+		method: String::from("GET"),
+		url: url.to_string(),
+		status: 200,
+		headers: Default::default(),
+		body: Body::Text(body.to_string()),
+	};
+	let cassette = Cassette {
+		interactions: vec![
+			interaction(
+				"https://svod-be.roosterteeth.com/api/v1/channels",
+				include_str!("../fixtures/channels.json"),
+			),
+			// Recorded with the query in a different order than Requests sends it.
+			interaction(
+				"https://svod-be.roosterteeth.com/api/v1/episodes?page=1&order=desc&per_page=100",
+				include_str!("../fixtures/episodes.json"),
+			),
+		],
+	};
+
+	let path = std::env::temp_dir().join(format!(
+		"roosterteeth-rs-cassette-{}.json",
+		std::process::id()
+	));
+	cassette.save(&path).unwrap();
+	let replayer = Replayer::load(&path).unwrap();
+	std::fs::remove_file(&path).unwrap();
+
+	let requests = Requests::new(Credential::Anonymous).with_transport(replayer);
+	assert_eq!(requests.list_channels(), fixture_catalog().channels);
+	assert_eq!(requests.list_episodes(1, None, None), fixture_episodes());
+
+	let missing = Replayer::new(Cassette::default()).send(reqwest::blocking::Request::new(
+		reqwest::Method::GET,
+		"https://svod-be.roosterteeth.com/api/v1/shows"
+			.parse()
+			.unwrap(),
+	));
+	assert_eq!(
+		missing.unwrap_err().to_string(),
+		"No recorded response for GET https://svod-be.roosterteeth.com/api/v1/shows"
+	);
+}
+
+#[test]
+fn resolve_missing_slugs() {
+	let interaction = |url: &str, status: u16, body: &str| Interaction {
+		method: String::from("GET"),
+		url: format!("https://svod-be.roosterteeth.com/api/v1{}", url),
+		status,
+		headers: Default::default(),
+		body: Body::Text(body.to_string()),
+	};
+	let cassette = Cassette {
+		interactions: vec![
+			interaction(
+				"/watch/red-vs-blue-season-1-episode-1",
+				200,
+				include_str!("../fixtures/episodes.json"),
+			),
+			interaction("/watch/no-such-episode", 404, r#"{"error": "Not Found"}"#),
+			interaction("/shows/no-such-show", 200, r#"{"data": []}"#),
+		],
+	};
+	let requests = Requests::new(Credential::Anonymous).with_transport(Replayer::new(cassette));
+
+	assert!(matches!(
+		requests.resolve("https://roosterteeth.com/watch/red-vs-blue-season-1-episode-1"),
+		Ok(Resolved::Episode(_))
+	));
+	assert_eq!(
+		requests.resolve("https://roosterteeth.com/watch/no-such-episode"),
+		Err(UrlError::NotFound(String::from(
+			"https://roosterteeth.com/watch/no-such-episode"
+		)))
+	);
+	assert!(matches!(
+		requests.resolve("https://roosterteeth.com/series/no-such-show"),
+		Err(UrlError::NotFound(_))
+	));
+}
+
+#[test]
+fn download_images() {
+	let episodes = fixture_episodes();
+	let found = &episodes[0].included.images[0];
+	let mut missing = found.clone();
+	missing.attributes.large = String::from("https://cdn.ruv.wtf/uploads/images/removed.jpg");
+	let interaction = |url: &str, status: u16, body: &str| Interaction {
+		method: String::from("GET"),
+		url: url.to_string(),
+		status,
+		headers: Default::default(),
+		body: Body::Text(body.to_string()),
+	};
+	let cassette = Cassette {
+		interactions: vec![
+			interaction(found.attributes.url(Size::Large), 200, "jpeg"),
+			interaction(
+				missing.attributes.url(Size::Large),
+				404,
+				"<h1>Not Found</h1>",
+			),
+		],
+	};
+	let requests = Requests::new(Credential::Anonymous).with_transport(Replayer::new(cassette));
+
+	assert_eq!(requests.get_image(found, Size::Large).unwrap(), b"jpeg");
+	// An error page isn't mistaken for the image.
+	assert_eq!(
+		requests
+			.get_image(&missing, Size::Large)
+			.unwrap_err()
+			.status,
+		404
+	);
+}
+
+#[test]
+fn artwork_is_retried() {
+	let episode = fixture_episodes().remove(0);
+	let artwork = nfo::episode_artwork(&episode, "S01E01");
+	let url = artwork[0].image.attributes.url(Size::Large);
+	let replay = |status: u16, body: &str| {
+		Requests::new(Credential::Anonymous).with_transport(Replayer::new(Cassette {
+			interactions: vec![Interaction {
+				method: String::from("GET"),
+				url: url.to_string(),
+				status,
+				headers: Default::default(),
+				body: Body::Text(body.to_string()),
+			}],
+		}))
+	};
+	let dir = std::env::temp_dir().join(format!("roosterteeth-rs-artwork-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let path = dir.join(artwork[0].file_name());
+
+	// A failed download leaves nothing behind, so the next export tries again.
+	assert!(nfo::write_artwork(&replay(403, "Forbidden"), &dir, &artwork).is_err());
+	assert!(!path.exists());
+
+	nfo::write_artwork(&replay(200, "jpeg"), &dir, &artwork).unwrap();
+	assert_eq!(std::fs::read(&path).unwrap(), b"jpeg");
+
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn replay_logged_in_session() {
+	let uuid = "a3c5e2f0-7d4b-4c1e-9f6a-2b8d0e1c7f34";
+	let interaction = |method: &str, url: &str, status: u16, body: &str| Interaction {
+		method: method.to_string(),
+		url: url.to_string(),
+		status,
+		headers: Default::default(),
+		body: Body::Text(body.to_string()),
+	};
+	let watchlist = format!(
+		"https://svod-be.roosterteeth.com/api/v1/users/{}/watchlist",
+		uuid
+	);
+	let cassette = Cassette {
+		interactions: vec![
+			interaction(
+				"POST",
+				"https://auth.roosterteeth.com/oauth/token",
+				200,
+				&serde_json::json!({
+					"access_token": "secret-access",
+					"token_type": "bearer",
+					"expires_in": 7200,
+					"refresh_token": "secret-refresh",
+					"scope": "user public",
+					"created_at": 1578500000,
+					"user_id": 4021387,
+					"uuid": uuid,
+				})
+				.to_string(),
+			),
+			interaction(
+				"GET",
+				&format!("{}?per_page=100&page=1", watchlist),
+				200,
+				include_str!("../fixtures/episodes.json"),
+			),
+			interaction(
+				"GET",
+				&format!("{}?per_page=100&page=2", watchlist),
+				200,
+				r#"{"data": [], "page": 2, "per_page": 100, "total_pages": 1, "total_results": 2}"#,
+			),
+			// The token has expired by the time the episode is removed.
+			interaction(
+				"DELETE",
+				&format!("{}/ffac28dc-464d-11e7-a302-065410f210c4", watchlist),
+				401,
+				r#"{"error": "unauthorized"}"#,
+			),
+		],
+	};
+
+	let path = std::env::temp_dir().join(format!(
+		"roosterteeth-rs-session-{}.json",
+		std::process::id()
+	));
+	let recorder = Recorder::new(Box::new(Replayer::new(cassette)), &path);
+	let requests = Requests::new_with_transport(Credential::Login("user", "pass"), recorder);
+
+	let episodes = requests.get_watchlist().unwrap();
+	assert_eq!(episodes, fixture_episodes());
+	assert_eq!(
+		requests.remove_from_watchlist(&episodes[0].uuid),
+		Err(UserError::Http(401))
+	);
+
+	// The recording replays the login, but without the tokens.
+	let recorded = Cassette::load(&path).unwrap();
+	std::fs::remove_file(&path).unwrap();
+	let login = serde_json::to_string(&recorded.interactions[0].body).unwrap();
+	assert!(!login.contains("secret"));
+
+	let requests =
+		Requests::new_with_transport(Credential::Login("user", "pass"), Replayer::new(recorded));
+	assert_eq!(requests.get_watchlist().unwrap(), episodes);
+}
+
+#[cfg(feature = "serve")]
+#[test]
+fn record_and_replay() {
+	use crate::serve::Server;
+
+	let path = std::env::temp_dir().join(format!(
+		"roosterteeth-rs-recording-{}.json",
+		std::process::id()
+	));
+	let catalog = fixture_catalog();
+
+	let server = Server::bind("127.0.0.1:0", catalog.clone())
+		.unwrap()
+		.spawn();
+	let api_url = server.api_url().to_string();
+	let recorded = Requests::new(Credential::Anonymous)
+		.with_api_url(&api_url)
+		.recording(&path)
+		.sync_catalog(None);
+	drop(server);
+
+	// The server is gone, so everything has to come from the cassette.
+	let requests = Requests::new(Credential::Anonymous)
+		.with_api_url(&api_url)
+		.with_transport(Replayer::load(&path).unwrap());
+	let replayed = requests.sync_catalog(None);
+	std::fs::remove_file(&path).unwrap();
+
+	assert_eq!(replayed.series, recorded.series);
+	assert_eq!(replayed.episodes, catalog.episodes);
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn trace_redaction() {
+	use crate::trace::{endpoint, redact_headers, redact_url, REDACTED};
+	use reqwest::header::{HeaderMap, HeaderValue};
+	use reqwest::Url;
+
+	let api = "https://svod-be.roosterteeth.com/api/v1";
+	let url: Url = format!("{}/seasons/red-vs-blue-season-1/episodes?page=2", api)
+		.parse()
+		.unwrap();
+	assert_eq!(
+		endpoint(&url, api),
+		(
+			String::from("seasons/{slug}/episodes"),
+			Some(String::from("red-vs-blue-season-1"))
+		)
+	);
+	assert_eq!(
+		endpoint(&format!("{}/channels", api).parse().unwrap(), api),
+		(String::from("channels"), None)
+	);
+	assert_eq!(
+		endpoint(&"https://cdn.example.com/image.jpg".parse().unwrap(), api).0,
+		"cdn.example.com"
+	);
+
+	let url: Url = "https://auth.roosterteeth.com/oauth/token?password=hunter2&scope=user"
+		.parse()
+		.unwrap();
+	let redacted = redact_url(&url);
+	assert!(!redacted.contains("hunter2"));
+	assert!(redacted.contains("scope=user"));
+
+	let mut token: HeaderValue = "Bearer secret".parse().unwrap();
+	token.set_sensitive(true);
+	let mut headers = HeaderMap::new();
+	headers.insert("authorization", token);
+	headers.insert("accept", "application/json".parse().unwrap());
+	let redacted = redact_headers(&headers);
+	assert!(redacted.contains(&(String::from("authorization"), REDACTED.to_string())));
+	assert!(redacted.contains(&(String::from("accept"), String::from("application/json"))));
+	assert!(!format!("{:?}", headers).contains("secret"));
+}
@@ -0,0 +1,157 @@
+//! Tracing spans for the calls `Requests` makes, enabled by the `tracing` feature.
+//!
+//! Every request runs inside a `request` span with the endpoint, slug, page, status, latency, bytes and retry
+//! count as fields. Secrets never reach the logs: sensitive headers such as the bearer token are replaced
+//! with `[redacted]`, as are query parameters that look like credentials.
+
+use reqwest::blocking::Request;
+use reqwest::header::HeaderMap;
+use reqwest::Url;
+use std::time::Instant;
+use tracing::field::Empty;
+use tracing::{debug, error, info_span, warn, Span};
+
+use crate::transport::{Error, Response};
+
+pub const REDACTED: &str = "[redacted]";
+
+/// Query parameters whose values are never logged.
+const SECRET_PARAMS: &[&str] = &[
+	"access_token",
+	"refresh_token",
+	"token",
+	"password",
+	"client_secret",
+];
+
+/// A request in progress, recorded into its span once it finishes.
+pub(crate) struct Call {
+	span: Span,
+	started: Instant,
+}
+
+impl Call {
+	pub(crate) fn start(request: &Request, api_url: &str) -> Call {
+		let url = redact_url(request.url());
+		let (endpoint, slug) = endpoint(request.url(), api_url);
+		let page = request
+			.url()
+			.query_pairs()
+			.find(|(key, _)| key == "page")
+			.map(|(_, value)| value.into_owned());
+
+		let span = info_span!(
+			"request",
+			method = %request.method(),
+			endpoint = %endpoint,
+			slug = slug.as_deref(),
+			page = page.as_deref(),
+			url = %url,
+			status = Empty,
+			latency_ms = Empty,
+			bytes = Empty,
+			// The client doesn't retry failed requests, so this is always 0 for now.
+			retries = 0u32,
+		);
+		span.in_scope(|| debug!(headers = ?redact_headers(request.headers()), "sending request"));
+
+		Call {
+			span,
+			started: Instant::now(),
+		}
+	}
+
+	pub(crate) fn finish(self, result: &Result<Response, Error>) {
+		let _entered = self.span.enter();
+		self.span
+			.record("latency_ms", self.started.elapsed().as_millis() as u64);
+
+		match result {
+			Ok(response) => {
+				self.span.record("status", response.status);
+				self.span.record("bytes", response.body.len() as u64);
+
+				if response.is_success() {
+					debug!("request finished");
+				} else {
+					warn!(status = response.status, "request was not successful");
+				}
+			}
+			Err(e) => error!(error = %e, "request failed"),
+		}
+	}
+}
+
+/// The endpoint of an api url with its slugs and ids replaced, such as `shows/{slug}/seasons`,
+/// along with the first slug. Urls outside the api, such as images and playlists, use their host.
+pub(crate) fn endpoint(url: &Url, api_url: &str) -> (String, Option<String>) {
+	let path = match url.as_str().strip_prefix(api_url) {
+		Some(rest) => rest.split(['?', '#']).next().unwrap_or_default(),
+		None => return (url.host_str().unwrap_or_default().to_string(), None),
+	};
+
+	let mut slug = None;
+	let segments: Vec<&str> = path
+		.split('/')
+		.filter(|segment| !segment.is_empty())
+		.enumerate()
+		.map(|(index, segment)| {
+			// Paths alternate between a collection and the slug or id of an item in it.
+			if index % 2 == 1 {
+				slug.get_or_insert_with(|| segment.to_string());
+				"{slug}"
+			} else {
+				segment
+			}
+		})
+		.collect();
+
+	(segments.join("/"), slug)
+}
+
+/// The url with the values of credential-like query parameters replaced.
+pub(crate) fn redact_url(url: &Url) -> String {
+	if !url
+		.query_pairs()
+		.any(|(key, _)| SECRET_PARAMS.contains(&key.as_ref()))
+	{
+		return url.to_string();
+	}
+
+	let query: Vec<(String, String)> = url
+		.query_pairs()
+		.map(|(key, value)| {
+			let value = if SECRET_PARAMS.contains(&key.as_ref()) {
+				REDACTED.to_string()
+			} else {
+				value.into_owned()
+			};
+
+			(key.into_owned(), value)
+		})
+		.collect();
+
+	let mut url = url.clone();
+	url.query_pairs_mut().clear().extend_pairs(query);
+	url.to_string()
+}
+
+/// Header names and values, with sensitive values such as the authorization header replaced.
+pub(crate) fn redact_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+	headers
+		.iter()
+		.map(|(name, value)| {
+			let value = if value.is_sensitive()
+				|| name == "authorization"
+				|| name == "cookie"
+				|| name == "set-cookie"
+			{
+				REDACTED.to_string()
+			} else {
+				value.to_str().unwrap_or(REDACTED).to_string()
+			};
+
+			(name.to_string(), value)
+		})
+		.collect()
+}
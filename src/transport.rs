@@ -0,0 +1,321 @@
+/*!
+How [Requests](../requests/struct.Requests.html) sends its http requests, which can be swapped out to record
+responses to a cassette file and replay them later without the network.
+
+Recording runs against the real API once:
+
+```no_run
+use roosterteeth_rs::requests::{Credential, Requests};
+
+let requests = Requests::new(Credential::Anonymous).recording("tests/cassettes/channels.json");
+requests.list_channels();
+```
+
+Tests then replay the cassette, so they are fast and deterministic:
+
+```no_run
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::transport::Replayer;
+
+let replayer = Replayer::load("tests/cassettes/channels.json").unwrap();
+let requests = Requests::new(Credential::Anonymous).with_transport(replayer);
+assert!(!requests.list_channels().is_empty());
+```
+
+Logged in sessions are recorded by passing the transport to
+[new_with_transport](../requests/struct.Requests.html#method.new_with_transport), so that logging in goes through it:
+
+```no_run
+use reqwest::blocking::Client;
+use roosterteeth_rs::requests::{Credential, Requests};
+use roosterteeth_rs::transport::{Http, Recorder};
+
+let recorder = Recorder::new(Box::new(Http::new(Client::new())), "tests/cassettes/watchlist.json");
+let requests = Requests::new_with_transport(Credential::Login("user", "pass"), recorder);
+requests.get_watchlist().unwrap();
+```
+
+Requests are matched on their method, url and query, where the order of the query parameters doesn't matter.
+Request headers and bodies are never recorded, and tokens in login responses are replaced, so that
+credentials don't end up in cassettes.
+*/
+
+use reqwest::blocking::{Client, Request};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+pub type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// Fields of a login response that are replaced before it is recorded.
+const TOKEN_FIELDS: &[&str] = &["access_token", "refresh_token"];
+
+/// A response read into memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+	pub status: u16,
+	/// Header values that aren't valid text are left out.
+	pub headers: BTreeMap<String, String>,
+	pub body: Vec<u8>,
+}
+
+impl Response {
+	pub fn is_success(&self) -> bool {
+		(200..300).contains(&self.status)
+	}
+
+	pub(crate) fn text(self) -> String {
+		String::from_utf8_lossy(&self.body).into_owned()
+	}
+}
+
+/// Sends requests on behalf of [Requests](../requests/struct.Requests.html).
+pub trait Transport: Send + Sync {
+	fn send(&self, request: Request) -> Result<Response, Error>;
+}
+
+/// Sends requests over the network, which is the default.
+pub struct Http {
+	client: Client,
+}
+
+impl Http {
+	pub fn new(client: Client) -> Self {
+		Http { client }
+	}
+}
+
+impl Transport for Http {
+	fn send(&self, request: Request) -> Result<Response, Error> {
+		let response = self.client.execute(request)?;
+
+		let status = response.status().as_u16();
+		let headers = response
+			.headers()
+			.iter()
+			.filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+			.collect();
+		let body = response.bytes()?.to_vec();
+
+		Ok(Response {
+			status,
+			headers,
+			body,
+		})
+	}
+}
+
+/// A recorded request and its response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+	pub method: String,
+	pub url: String,
+	pub status: u16,
+	pub headers: BTreeMap<String, String>,
+	pub body: Body,
+}
+
+/// Bodies are kept as text where possible, so cassettes are easy to read and edit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Body {
+	Text(String),
+	Bytes(Vec<u8>),
+}
+
+impl Body {
+	fn new(body: &[u8]) -> Body {
+		match std::str::from_utf8(body) {
+			Ok(text) => Body::Text(text.to_string()),
+			Err(_) => Body::Bytes(body.to_vec()),
+		}
+	}
+
+	fn to_vec(&self) -> Vec<u8> {
+		match self {
+			Body::Text(text) => text.as_bytes().to_vec(),
+			Body::Bytes(bytes) => bytes.clone(),
+		}
+	}
+}
+
+/// The interactions saved by a [Recorder](./struct.Recorder.html), in the order they happened.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cassette {
+	pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+	pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Cassette> {
+		let reader = BufReader::new(File::open(path)?);
+
+		serde_json::from_reader(reader).map_err(io::Error::from)
+	}
+
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		if let Some(parent) = path.as_ref().parent() {
+			fs::create_dir_all(parent)?;
+		}
+		let mut writer = BufWriter::new(File::create(path)?);
+
+		serde_json::to_writer_pretty(&mut writer, self)?;
+		writer.flush()
+	}
+}
+
+/// Passes requests on to another transport, saving each interaction to a cassette as it happens.
+pub struct Recorder {
+	inner: Box<dyn Transport>,
+	path: PathBuf,
+	cassette: Mutex<Cassette>,
+}
+
+impl Recorder {
+	/// Starts a new cassette, replacing the file if it already exists.
+	pub fn new<P: AsRef<Path>>(inner: Box<dyn Transport>, path: P) -> Self {
+		Recorder {
+			inner,
+			path: path.as_ref().to_path_buf(),
+			cassette: Mutex::new(Cassette::default()),
+		}
+	}
+}
+
+impl Transport for Recorder {
+	fn send(&self, request: Request) -> Result<Response, Error> {
+		let method = request.method().to_string();
+		let url = request.url().to_string();
+
+		let response = self.inner.send(request)?;
+
+		let mut cassette = self.cassette.lock().unwrap();
+		cassette.interactions.push(Interaction {
+			method,
+			url,
+			status: response.status,
+			headers: response.headers.clone(),
+			body: Body::new(&without_tokens(&response.body)),
+		});
+		cassette.save(&self.path)?;
+
+		Ok(response)
+	}
+}
+
+/// Answers requests from a cassette, without touching the network.
+///
+/// When a request was recorded several times, the responses are replayed in order and the last one repeats.
+pub struct Replayer {
+	interactions: Vec<(String, Interaction)>,
+	played: Mutex<BTreeMap<String, usize>>,
+}
+
+/// Returned when a request isn't in the cassette being replayed.
+#[derive(Debug, Clone)]
+pub struct NotRecorded {
+	pub method: String,
+	pub url: String,
+}
+
+impl fmt::Display for NotRecorded {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "No recorded response for {} {}", self.method, self.url)
+	}
+}
+
+impl error::Error for NotRecorded {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		None
+	}
+}
+
+impl Replayer {
+	pub fn new(cassette: Cassette) -> Self {
+		Replayer {
+			interactions: cassette
+				.interactions
+				.into_iter()
+				.map(|interaction| (key(&interaction.method, &interaction.url), interaction))
+				.collect(),
+			played: Mutex::new(BTreeMap::new()),
+		}
+	}
+
+	pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+		Cassette::load(path).map(Replayer::new)
+	}
+}
+
+impl Transport for Replayer {
+	fn send(&self, request: Request) -> Result<Response, Error> {
+		let method = request.method().to_string();
+		let url = request.url().to_string();
+		let key = key(&method, &url);
+
+		let matches: Vec<&Interaction> = self
+			.interactions
+			.iter()
+			.filter(|(k, _)| *k == key)
+			.map(|(_, interaction)| interaction)
+			.collect();
+		if matches.is_empty() {
+			return Err(Box::new(NotRecorded { method, url }));
+		}
+
+		let mut played = self.played.lock().unwrap();
+		let count = played.entry(key).or_insert(0);
+		let interaction = matches[(*count).min(matches.len() - 1)];
+		*count += 1;
+
+		Ok(Response {
+			status: interaction.status,
+			headers: interaction.headers.clone(),
+			body: interaction.body.to_vec(),
+		})
+	}
+}
+
+/// The body with the tokens of a login response replaced, which still parses when replayed.
+fn without_tokens(body: &[u8]) -> Vec<u8> {
+	let mut value: serde_json::Value = match serde_json::from_slice(body) {
+		Ok(value) => value,
+		Err(_) => return body.to_vec(),
+	};
+
+	let object = match value.as_object_mut() {
+		Some(object) if TOKEN_FIELDS.iter().any(|field| object.contains_key(*field)) => object,
+		_ => return body.to_vec(),
+	};
+	for field in TOKEN_FIELDS {
+		if let Some(token) = object.get_mut(*field) {
+			*token = serde_json::Value::from("[redacted]");
+		}
+	}
+
+	serde_json::to_vec(&value).unwrap()
+}
+
+/// The method and url with the query parameters sorted, so that their order doesn't affect matching.
+fn key(method: &str, url: &str) -> String {
+	let mut url = match Url::parse(url) {
+		Ok(url) => url,
+		Err(_) => return format!("{} {}", method, url),
+	};
+
+	let mut query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+	query.sort();
+	url.set_fragment(None);
+	if query.is_empty() {
+		url.set_query(None);
+	} else {
+		url.query_pairs_mut().clear().extend_pairs(query);
+	}
+
+	format!("{} {}", method.to_uppercase(), url)
+}
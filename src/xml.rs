@@ -0,0 +1,27 @@
+//! Helpers shared by the hand written XML of the feed and NFO exports.
+
+pub(crate) fn push_element(xml: &mut String, depth: usize, name: &str, text: &str) {
+	for _ in 0..depth {
+		xml.push('\t');
+	}
+
+	xml.push_str(&format!("<{}>{}</{}>\n", name, escape(text), name));
+}
+
+/// Escapes text for use in both XML elements and attributes.
+pub(crate) fn escape(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+
+	for c in text.chars() {
+		match c {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&apos;"),
+			_ => escaped.push(c),
+		}
+	}
+
+	escaped
+}